@@ -1,15 +1,37 @@
-use std::fs::File;
 use std::path::Path;
+use std::process;
 
 use raytracer_rust::renderer::Renderer;
 use raytracer_rust::scene::Scene;
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let spectral = args.iter().any(|arg| arg == "--spectral");
+    let scene_path = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .map_or("data/scene.json", String::as_str);
+    let scene_path = Path::new(scene_path);
+
     println!("Loading scene...");
-    let scene_file = File::open("data/scene.json").unwrap();
-    let scene: Scene = serde_json::from_reader(scene_file).unwrap();
+    let scene = match Scene::load(scene_path) {
+        Ok(scene) => scene,
+        Err(error) => {
+            eprintln!("Cannot load scene: {error}");
+            process::exit(1);
+        }
+    };
+    println!(
+        "Loaded {} textures ({:.1} MiB)",
+        scene.textures.len(),
+        scene.textures.memory_usage() as f64 / (1024. * 1024.)
+    );
 
-    let screen = Renderer::default().render(&scene);
+    let renderer = Renderer {
+        spectral,
+        ..Renderer::default()
+    };
+    let screen = renderer.render(&scene);
 
     screen.save(Path::new("tmp/scene.png")).unwrap();
 }
@@ -6,6 +6,7 @@ use crate::scene::Scene;
 use crate::utils::hit::{HitInfo, Hittable};
 use crate::utils::random;
 use crate::utils::ray::Ray;
+use crate::utils::spectrum::{sample_wavelength, upsample, wavelength_to_rgb};
 use image::{Rgb, RgbImage};
 use indicatif::ParallelProgressIterator;
 use palette::{LinSrgb, Srgb};
@@ -15,6 +16,7 @@ pub struct Renderer {
     pub rays_per_pixel: usize,
     pub tile_size: usize,
     pub max_bounces: usize,
+    pub spectral: bool,
 }
 
 impl Default for Renderer {
@@ -23,6 +25,7 @@ impl Default for Renderer {
             rays_per_pixel: 512,
             tile_size: 16,
             max_bounces: 16,
+            spectral: false,
         }
     }
 }
@@ -109,7 +112,11 @@ impl Renderer {
             let lens_offset = random::get_random_in_disk();
             let ray = scene.camera.get_ray(lens_offset, (u, v));
 
-            color += self.render_ray(scene, ray);
+            color += if self.spectral {
+                self.render_spectral_ray(scene, ray)
+            } else {
+                self.render_ray(scene, ray)
+            };
         }
         color.red /= self.rays_per_pixel as f32;
         color.green /= self.rays_per_pixel as f32;
@@ -126,7 +133,7 @@ impl Renderer {
             if let Some(hit) = scene.hit(ray, TOLERANCE, f64::INFINITY) {
                 let hit_info = HitInfo::from(hit);
                 albedo *= hit_info.color;
-                if let Materials::Light(_) = hit_info.hit.object.material {
+                if let Materials::Light(_) = *hit_info.hit.object.material {
                     color += albedo
                 }
                 if let Some(next_ray) = hit_info.next_ray {
@@ -138,4 +145,32 @@ impl Renderer {
         }
         color
     }
+
+    fn render_spectral_ray(&self, scene: &Scene, ray: Ray) -> LinSrgb {
+        let wavelength = sample_wavelength();
+        let mut ray = Ray {
+            wavelength: Some(wavelength),
+            ..ray
+        };
+        let mut throughput = 1.;
+        let mut radiance = 0.;
+        for _ in 0..self.max_bounces {
+            if let Some(hit) = scene.hit(ray, TOLERANCE, f64::INFINITY) {
+                let hit_info = HitInfo::from(hit);
+                if let Materials::Light(light) = &*hit_info.hit.object.material {
+                    let (ray, t) = (hit_info.hit.ray, hit_info.hit.t);
+                    throughput *= light.radiance(ray, t, &hit_info.shape_hit, wavelength);
+                    radiance += throughput
+                } else {
+                    throughput *= upsample(hit_info.color, wavelength);
+                }
+                if let Some(next_ray) = hit_info.next_ray {
+                    ray = next_ray;
+                } else {
+                    break;
+                }
+            }
+        }
+        wavelength_to_rgb(wavelength) * radiance as f32
+    }
 }
@@ -1,7 +1,6 @@
 use crate::utils::point::{Point, PointAsArray};
 use crate::utils::ray::Ray;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(from = "CameraConfig", into = "CameraConfig")]
@@ -76,6 +75,7 @@ impl From<CameraConfig> for Camera {
 }
 
 impl Camera {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         position: Point,
         target: Point,
@@ -116,6 +116,12 @@ impl Camera {
 }
 
 impl Camera {
+    pub fn pixel_spread(&self) -> f64 {
+        self.screen_horizontal.norm()
+            / self.screen_width as f64
+            / (self.target - self.position).norm()
+    }
+
     pub fn get_ray(&self, lens_offset: (f64, f64), screen_offset: (f64, f64)) -> Ray {
         let origin = self.position + self.left * lens_offset.0 + self.up * lens_offset.1;
         let target = self.screen_origin
@@ -125,6 +131,9 @@ impl Camera {
         Ray {
             origin,
             direction: target - origin,
+            width: 0.,
+            spread: self.pixel_spread(),
+            wavelength: None,
         }
     }
 }
@@ -176,11 +185,15 @@ mod test {
             900,
         );
 
+        assert!(approx::relative_eq!(camera.pixel_spread(), 16. / 1600. / 8.));
         assert_eq!(
             camera.get_ray((0., 0.), (0.5, 0.5)),
             Ray {
                 origin: camera.position,
-                direction: camera.target - camera.position
+                direction: camera.target - camera.position,
+                width: 0.,
+                spread: camera.pixel_spread(),
+                wavelength: None,
             }
         );
         assert_eq!(
@@ -188,6 +201,9 @@ mod test {
             Ray {
                 origin: Point::from_xyz(0., -0.02, 1.5 + 0.02),
                 direction: Point::from_xyz(8., 16. / 4. + 0.02, -9. / 4. - 0.02),
+                width: 0.,
+                spread: camera.pixel_spread(),
+                wavelength: None,
             }
         );
     }
@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum SceneError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    MissingTexture {
+        path: PathBuf,
+    },
+    InvalidTexture {
+        path: PathBuf,
+        message: String,
+    },
+    UnsupportedPixelFormat {
+        path: PathBuf,
+        format: String,
+    },
+    Obj {
+        path: PathBuf,
+        message: String,
+    },
+    Gltf {
+        path: PathBuf,
+        message: String,
+    },
+    UnknownMaterial(String),
+    UnknownGroup(String),
+    RecursiveGroup(String),
+    InvalidObject(String),
+}
+
+impl SceneError {
+    pub fn json(path: PathBuf, error: serde_json::Error) -> SceneError {
+        let (line, column) = (error.line(), error.column());
+        let message = error.to_string();
+        let message = message
+            .strip_suffix(&format!(" at line {line} column {column}"))
+            .unwrap_or(&message)
+            .to_owned();
+        SceneError::Json {
+            path,
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SceneError::Io { path, source } => {
+                write!(f, "cannot read {}: {source}", path.display())
+            }
+            SceneError::Json {
+                path,
+                line,
+                column,
+                message,
+            } => write!(f, "{}:{line}:{column}: {message}", path.display()),
+            SceneError::MissingTexture { path } => {
+                write!(f, "texture {} not found", path.display())
+            }
+            SceneError::InvalidTexture { path, message } => {
+                write!(f, "cannot decode texture {}: {message}", path.display())
+            }
+            SceneError::UnsupportedPixelFormat { path, format } => write!(
+                f,
+                "texture {} has unsupported pixel format {format}",
+                path.display()
+            ),
+            SceneError::Obj { path, message } | SceneError::Gltf { path, message } => {
+                write!(f, "cannot load {}: {message}", path.display())
+            }
+            SceneError::UnknownMaterial(name) => write!(f, "unknown material '{name}'"),
+            SceneError::UnknownGroup(name) => write!(f, "unknown group '{name}'"),
+            SceneError::RecursiveGroup(name) => write!(f, "group '{name}' contains itself"),
+            SceneError::InvalidObject(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for SceneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SceneError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_json_error() {
+        let error = serde_json::from_str::<Vec<f64>>("[1,\n 2,\n x]").unwrap_err();
+        let error = SceneError::json(PathBuf::from("scene.json"), error);
+
+        assert!(matches!(
+            error,
+            SceneError::Json {
+                line: 3,
+                column: 2,
+                ..
+            }
+        ));
+        assert_eq!(error.to_string(), "scene.json:3:2: expected value");
+    }
+}
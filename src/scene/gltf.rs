@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ::gltf::image::{Data, Format};
+use ::gltf::mesh::Mode;
+use ::gltf::{Document, Node};
+use image::DynamicImage::{
+    ImageLuma16, ImageLuma8, ImageLumaA16, ImageLumaA8, ImageRgb16, ImageRgb32F, ImageRgb8,
+    ImageRgba16, ImageRgba32F, ImageRgba8,
+};
+use image::{ImageBuffer, Rgba32FImage};
+use palette::LinSrgb;
+
+use super::camera::Camera;
+use super::error::SceneError;
+use super::object::colors::combinators::Multiply;
+use super::object::colors::texture::{linearize, ColorSpace, Texture};
+use super::object::colors::uniform::Uniform;
+use super::object::colors::Colors;
+use super::object::materials::conductor::{Conductor, Fresnel};
+use super::object::materials::dielectric::Dielectric;
+use super::object::materials::diffuse::Diffuse;
+use super::object::materials::light::Light;
+use super::object::materials::Materials;
+use super::object::normal_map::NormalMap;
+use super::object::shapes::mesh::Mesh;
+use super::object::shapes::sky::Sky;
+use super::object::shapes::transformed::Transformed;
+use super::object::shapes::Shapes;
+use super::object::Object;
+use super::textures::TextureRegistry;
+use super::Scene;
+use crate::utils::point::Point;
+use crate::utils::transform::{transpose, Matrix, Transform};
+
+const SCREEN_WIDTH: usize = 1600;
+const DEFAULT_ASPECT_RATIO: f64 = 16. / 9.;
+
+// glTF is Y-up while scenes are Z-up.
+const Y_UP_TO_Z_UP: Matrix = [
+    [1., 0., 0., 0.],
+    [0., 0., -1., 0.],
+    [0., 1., 0., 0.],
+    [0., 0., 0., 1.],
+];
+
+struct Images {
+    path: PathBuf,
+    data: Vec<Data>,
+    textures: TextureRegistry,
+}
+
+struct Loader {
+    document: Document,
+    buffers: Vec<::gltf::buffer::Data>,
+    images: Images,
+
+    shapes: HashMap<usize, Vec<Option<Arc<Shapes>>>>,
+    objects: Vec<Object>,
+    camera: Option<Camera>,
+}
+
+pub fn load(path: &Path) -> Result<Scene, SceneError> {
+    let error = |message: String| SceneError::Gltf {
+        path: path.to_owned(),
+        message,
+    };
+    let (document, buffers, images) =
+        ::gltf::import(path).map_err(|source| error(source.to_string()))?;
+    let mut loader = Loader {
+        document,
+        buffers,
+        images: Images {
+            path: path.to_owned(),
+            data: images,
+            textures: TextureRegistry::default(),
+        },
+        shapes: HashMap::new(),
+        objects: Vec::new(),
+        camera: None,
+    };
+
+    let scene = loader
+        .document
+        .default_scene()
+        .or_else(|| loader.document.scenes().next())
+        .ok_or_else(|| error("file contains no scene".to_owned()))?
+        .index();
+    let nodes: Vec<usize> = loader
+        .document
+        .scenes()
+        .nth(scene)
+        .unwrap()
+        .nodes()
+        .map(|node| node.index())
+        .collect();
+    for node in nodes {
+        loader
+            .load_node(node, &Transform::from_matrix(Y_UP_TO_Z_UP).unwrap())
+            .map_err(error)?;
+    }
+
+    let Loader {
+        mut objects,
+        camera,
+        images,
+        ..
+    } = loader;
+    // Files without emissive materials would render black, so they get a white sky instead.
+    let is_lit = objects
+        .iter()
+        .any(|object| matches!(*object.material, Materials::Light(_)));
+    if !is_lit {
+        objects.push(Object {
+            shape: Shapes::Sky(Sky::default()),
+            material: Arc::new(Materials::Light(Light::new(
+                Colors::Uniform(Uniform {
+                    color: LinSrgb::new(1., 1., 1.),
+                }),
+                1.,
+            ))),
+            normal_map: None,
+        });
+    }
+
+    let mut scene = Scene::new(default_camera(), objects, images.textures);
+    scene.camera = camera.unwrap_or_else(|| framing_camera(&scene));
+    Ok(scene)
+}
+
+impl Images {
+    fn texture(&mut self, index: usize, color_space: ColorSpace) -> Result<Texture, String> {
+        let path = PathBuf::from(format!("{}#image{index}", self.path.display()));
+        let image = self.textures.get_or_insert_with(&path, color_space, || {
+            convert_image(&self.data[index], color_space)
+        })?;
+        let mut texture = Texture::new(path, image);
+        texture.color_space = color_space;
+        Ok(texture)
+    }
+}
+
+impl Loader {
+    fn load_node(&mut self, index: usize, parent: &Transform) -> Result<(), String> {
+        let node = self.document.nodes().nth(index).unwrap();
+        let matrix = transpose(&node.transform().matrix().map(|c| c.map(f64::from)));
+        let transform = *parent
+            * Transform::from_matrix(matrix)
+                .ok_or_else(|| format!("node {index} has a singular transform"))?;
+
+        if self.camera.is_none() {
+            self.camera = load_camera(&node, &transform);
+        }
+        if let Some(mesh) = node.mesh() {
+            if !self.shapes.contains_key(&mesh.index()) {
+                let shapes = mesh
+                    .primitives()
+                    .map(|primitive| self.load_primitive(&primitive))
+                    .collect::<Result<_, _>>()?;
+                self.shapes.insert(mesh.index(), shapes);
+            }
+            let shapes = self.shapes[&mesh.index()].clone();
+            for (primitive, shape) in mesh.primitives().zip(shapes) {
+                if let Some(shape) = shape {
+                    let material = load_material(&primitive.material(), &mut self.images)?;
+                    let normal_map = load_normal_map(&primitive.material(), &mut self.images)?;
+                    self.objects.push(Object {
+                        shape: Shapes::Transformed(Transformed { transform, shape }),
+                        material: Arc::new(material),
+                        normal_map: normal_map.map(Arc::new),
+                    });
+                }
+            }
+        }
+
+        let children: Vec<usize> = node.children().map(|child| child.index()).collect();
+        for child in children {
+            self.load_node(child, &transform)?;
+        }
+        Ok(())
+    }
+
+    fn load_primitive(&self, primitive: &::gltf::Primitive) -> Result<Option<Arc<Shapes>>, String> {
+        if primitive.mode() != Mode::Triangles {
+            return Ok(None);
+        }
+        let reader = primitive.reader(|buffer| Some(&self.buffers[buffer.index()]));
+        let Some(positions) = reader.read_positions() else {
+            return Ok(None);
+        };
+
+        let vertices: Vec<Point> = positions
+            .map(|p| Point::from_xyz(p[0] as f64, p[1] as f64, p[2] as f64))
+            .collect();
+        let normals: Vec<Point> = match reader.read_normals() {
+            Some(normals) => normals
+                .map(|n| Point::from_xyz(n[0] as f64, n[1] as f64, n[2] as f64))
+                .collect(),
+            None => Vec::new(),
+        };
+        let uvs: Vec<(f64, f64)> = match reader.read_tex_coords(0) {
+            Some(uvs) => uvs
+                .into_f32()
+                .map(|uv| (uv[0] as f64, uv[1] as f64))
+                .collect(),
+            None => Vec::new(),
+        };
+        let indices: Vec<usize> = match reader.read_indices() {
+            Some(indices) => indices.into_u32().map(|i| i as usize).collect(),
+            None => (0..vertices.len()).collect(),
+        };
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|face| [face[0], face[1], face[2]])
+            .collect();
+
+        Ok(Some(Arc::new(Shapes::Mesh(Mesh::new(
+            vertices, normals, uvs, triangles,
+        )?))))
+    }
+}
+
+fn load_material(material: &::gltf::Material, images: &mut Images) -> Result<Materials, String> {
+    let emissive = material.emissive_factor();
+    let emissive_strength = material.emissive_strength().unwrap_or(1.);
+    if emissive.iter().any(|&x| x > 0.) {
+        return Ok(Materials::Light(Light::new(
+            Colors::Uniform(Uniform {
+                color: LinSrgb::new(emissive[0], emissive[1], emissive[2]),
+            }),
+            emissive_strength,
+        )));
+    }
+
+    if material
+        .transmission()
+        .is_some_and(|transmission| transmission.transmission_factor() > 0.)
+    {
+        return Ok(Materials::Dielectric(Dielectric {
+            roughness: material.pbr_metallic_roughness().roughness_factor() as f64,
+            ..Dielectric::new(material.ior().unwrap_or(1.5) as f64)
+        }));
+    }
+
+    let pbr = material.pbr_metallic_roughness();
+    let base_color = pbr.base_color_factor();
+    let factor = Colors::Uniform(Uniform {
+        color: LinSrgb::new(base_color[0], base_color[1], base_color[2]),
+    });
+    let color = match pbr.base_color_texture() {
+        Some(info) => {
+            let texture =
+                Colors::Texture(images.texture(info.texture().source().index(), ColorSpace::Srgb)?);
+            if base_color[..3] == [1., 1., 1.] {
+                texture
+            } else {
+                Colors::Multiply(Multiply {
+                    first: Box::new(texture),
+                    second: Box::new(factor),
+                })
+            }
+        }
+        None => factor,
+    };
+
+    if pbr.metallic_factor() >= 0.5 {
+        Ok(Materials::Conductor(Conductor {
+            fresnel: Fresnel::Reflectance(color),
+            roughness: pbr.roughness_factor() as f64,
+        }))
+    } else {
+        Ok(Materials::Diffuse(Diffuse { color }))
+    }
+}
+
+fn load_normal_map(
+    material: &::gltf::Material,
+    images: &mut Images,
+) -> Result<Option<NormalMap>, String> {
+    material
+        .normal_texture()
+        .map(|info| {
+            let texture = images.texture(info.texture().source().index(), ColorSpace::Linear)?;
+            Ok(NormalMap::Normal {
+                texture,
+                strength: info.scale() as f64,
+            })
+        })
+        .transpose()
+}
+
+fn load_camera(node: &Node, transform: &Transform) -> Option<Camera> {
+    let camera = node.camera()?;
+    let ::gltf::camera::Projection::Perspective(perspective) = camera.projection() else {
+        return None;
+    };
+    let aspect_ratio = perspective
+        .aspect_ratio()
+        .map_or(DEFAULT_ASPECT_RATIO, f64::from);
+    let field_of_view = 2. * ((perspective.yfov() as f64 / 2.).tan() * aspect_ratio).atan();
+
+    let position = transform.apply_point(&Point::default());
+    let forward = transform
+        .apply_vector(&Point::from_xyz(0., 0., -1.))
+        .normalized();
+    let up = transform.apply_vector(&Point::from_xyz(0., 1., 0.));
+    Some(Camera::new(
+        position,
+        position + forward,
+        up,
+        field_of_view.to_degrees(),
+        0.,
+        1.,
+        SCREEN_WIDTH,
+        (SCREEN_WIDTH as f64 / aspect_ratio).round() as usize,
+    ))
+}
+
+fn default_camera() -> Camera {
+    Camera::new(
+        Point::from_xyz(-1., 0., 0.),
+        Point::default(),
+        Point::from_xyz(0., 0., 1.),
+        60.,
+        0.,
+        1.,
+        SCREEN_WIDTH,
+        (SCREEN_WIDTH as f64 / DEFAULT_ASPECT_RATIO).round() as usize,
+    )
+}
+
+fn framing_camera(scene: &Scene) -> Camera {
+    let bounds = scene.bounding_box();
+    if bounds.is_empty() {
+        return default_camera();
+    }
+    let target = bounds.centroid();
+    let distance = bounds.size().norm().max(1.);
+
+    Camera::new(
+        target + Point::from_xyz(-distance, 0., distance / 2.),
+        target,
+        Point::from_xyz(0., 0., 1.),
+        60.,
+        0.,
+        1.,
+        SCREEN_WIDTH,
+        (SCREEN_WIDTH as f64 / DEFAULT_ASPECT_RATIO).round() as usize,
+    )
+}
+
+fn convert_image(data: &Data, color_space: ColorSpace) -> Result<Rgba32FImage, String> {
+    let (width, height) = (data.width, data.height);
+    let bytes = data.pixels.as_slice();
+    let words = || -> Vec<u16> {
+        bytes
+            .chunks_exact(2)
+            .map(|word| u16::from_le_bytes([word[0], word[1]]))
+            .collect()
+    };
+    let floats = || -> Vec<f32> {
+        bytes
+            .chunks_exact(4)
+            .map(|word| f32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect()
+    };
+
+    let image = match data.format {
+        Format::R8 => ImageBuffer::from_raw(width, height, bytes.to_vec()).map(ImageLuma8),
+        Format::R8G8 => ImageBuffer::from_raw(width, height, bytes.to_vec()).map(ImageLumaA8),
+        Format::R8G8B8 => ImageBuffer::from_raw(width, height, bytes.to_vec()).map(ImageRgb8),
+        Format::R8G8B8A8 => ImageBuffer::from_raw(width, height, bytes.to_vec()).map(ImageRgba8),
+        Format::R16 => ImageBuffer::from_raw(width, height, words()).map(ImageLuma16),
+        Format::R16G16 => ImageBuffer::from_raw(width, height, words()).map(ImageLumaA16),
+        Format::R16G16B16 => ImageBuffer::from_raw(width, height, words()).map(ImageRgb16),
+        Format::R16G16B16A16 => ImageBuffer::from_raw(width, height, words()).map(ImageRgba16),
+        Format::R32G32B32FLOAT => ImageBuffer::from_raw(width, height, floats()).map(ImageRgb32F),
+        Format::R32G32B32A32FLOAT => {
+            ImageBuffer::from_raw(width, height, floats()).map(ImageRgba32F)
+        }
+    };
+    image
+        .map(|image| linearize(image, color_space))
+        .ok_or_else(|| "glTF image data does not match its dimensions".to_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::shapes::Shape;
+    use std::fs;
+
+    const TRIANGLE: &str = r#"{
+        "asset": {"version": "2.0"},
+        "scene": 0,
+        "scenes": [{"nodes": [0, 1]}],
+        "nodes": [
+            {"mesh": 0, "translation": [0, 0, -2]},
+            {"camera": 0, "translation": [0, 0, 1]}
+        ],
+        "cameras": [{"type": "perspective", "perspective": {"yfov": 1.0, "aspectRatio": 2.0, "znear": 0.1}}],
+        "meshes": [{"primitives": [{"attributes": {"POSITION": 0}, "indices": 1, "material": 0}]}],
+        "materials": [{"pbrMetallicRoughness": {"baseColorFactor": [1, 0, 0, 1], "metallicFactor": 1, "roughnessFactor": 0.2}}],
+        "buffers": [{"byteLength": 44, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAABAAIAAAA="}],
+        "bufferViews": [
+            {"buffer": 0, "byteOffset": 0, "byteLength": 36},
+            {"buffer": 0, "byteOffset": 36, "byteLength": 6}
+        ],
+        "accessors": [
+            {"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0]},
+            {"bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_load() {
+        let directory = std::env::temp_dir().join("raytracer_rust_test_gltf");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("triangle.gltf"), TRIANGLE).unwrap();
+
+        let scene = load(&directory.join("triangle.gltf")).unwrap();
+        assert_eq!(scene.objects.len(), 2);
+        assert!(matches!(
+            *scene.objects[0].material,
+            Materials::Conductor(_)
+        ));
+        assert_eq!(
+            scene.objects[0].shape.bounding_box().max,
+            Point::from_xyz(1., 2., 1.)
+        );
+        assert_eq!(
+            (scene.camera.screen_width, scene.camera.screen_height),
+            (1600, 800)
+        );
+    }
+
+    #[test]
+    fn test_materials() {
+        let directory = std::env::temp_dir().join("raytracer_rust_test_gltf");
+        fs::create_dir_all(&directory).unwrap();
+        let materials = r#""materials": [{"pbrMetallicRoughness": {"baseColorFactor": [1, 0, 0, 1], "metallicFactor": 1, "roughnessFactor": 0.2}}]"#;
+        let textured = TRIANGLE.replace(
+            materials,
+            r#""materials": [{
+                "pbrMetallicRoughness": {"baseColorFactor": [0.5, 1, 1, 1], "baseColorTexture": {"index": 0}, "metallicFactor": 0},
+                "normalTexture": {"index": 0, "scale": 0.5}
+            }],
+            "textures": [{"source": 0}],
+            "images": [{"uri": "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4//8/AAX+Av4N70a4AAAAAElFTkSuQmCC"}]"#,
+        );
+        let emissive =
+            TRIANGLE.replace(materials, r#""materials": [{"emissiveFactor": [1, 1, 1]}]"#);
+        fs::write(directory.join("textured.gltf"), textured).unwrap();
+        fs::write(directory.join("emissive.gltf"), emissive).unwrap();
+
+        let scene = load(&directory.join("textured.gltf")).unwrap();
+        assert_eq!(scene.objects.len(), 2);
+        let Materials::Diffuse(Diffuse {
+            color: Colors::Multiply(multiply),
+        }) = &*scene.objects[0].material
+        else {
+            panic!("base color factor must multiply the texture");
+        };
+        assert!(matches!(*multiply.first, Colors::Texture(_)));
+        assert!(matches!(
+            scene.objects[0].normal_map.as_deref(),
+            Some(NormalMap::Normal { strength, .. }) if *strength == 0.5
+        ));
+
+        let scene = load(&directory.join("emissive.gltf")).unwrap();
+        assert_eq!(scene.objects.len(), 1);
+        assert!(matches!(*scene.objects[0].material, Materials::Light(_)));
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::error::SceneError;
+use super::object::materials::{MaterialRef, Materials};
+use super::object::normal_map::NormalMap;
+use super::object::shapes::transformed::Transformed;
+use super::object::shapes::Shapes;
+use super::object::{Object, ObjectConfig};
+use super::textures::TextureRegistry;
+use crate::utils::transform::Transform;
+
+#[derive(Debug, Clone)]
+pub struct Instance {
+    pub shape: Arc<Shapes>,
+    pub transform: Transform,
+    pub material: Option<Arc<Materials>>,
+    pub normal_map: Option<Arc<NormalMap>>,
+}
+
+impl Instance {
+    pub fn new(
+        shape: Shapes,
+        material: Option<Arc<Materials>>,
+        normal_map: Option<Arc<NormalMap>>,
+    ) -> Instance {
+        Instance {
+            shape: Arc::new(shape),
+            transform: Transform::identity(),
+            material,
+            normal_map,
+        }
+    }
+
+    pub fn placed(
+        &self,
+        transform: &Transform,
+        material: Option<&Arc<Materials>>,
+        normal_map: Option<&Arc<NormalMap>>,
+    ) -> Instance {
+        Instance {
+            shape: self.shape.clone(),
+            transform: *transform * self.transform,
+            material: self.material.clone().or_else(|| material.cloned()),
+            normal_map: self.normal_map.clone().or_else(|| normal_map.cloned()),
+        }
+    }
+
+    pub fn into_object(self) -> Result<Object, SceneError> {
+        let material = self.material.ok_or_else(|| {
+            SceneError::InvalidObject("object has no material and inherits none".to_owned())
+        })?;
+        let shape = if self.transform == Transform::identity() {
+            Arc::try_unwrap(self.shape).unwrap_or_else(|shape| {
+                Shapes::Transformed(Transformed {
+                    transform: self.transform,
+                    shape,
+                })
+            })
+        } else {
+            Shapes::Transformed(Transformed {
+                transform: self.transform,
+                shape: self.shape,
+            })
+        };
+
+        Ok(Object {
+            shape,
+            material,
+            normal_map: self.normal_map,
+        })
+    }
+}
+
+enum Group {
+    Pending(Box<ObjectConfig>),
+    Building,
+    Built(Vec<Instance>),
+}
+
+pub struct Groups {
+    groups: HashMap<String, Group>,
+    materials: HashMap<String, Arc<Materials>>,
+    textures: TextureRegistry,
+}
+
+impl Groups {
+    pub fn new(
+        groups: HashMap<String, ObjectConfig>,
+        materials: HashMap<String, Materials>,
+    ) -> Result<Groups, SceneError> {
+        let mut textures = TextureRegistry::default();
+        let materials = materials
+            .into_iter()
+            .map(|(name, mut material)| {
+                material.load_textures(&mut textures)?;
+                Ok((name, Arc::new(material)))
+            })
+            .collect::<Result<_, SceneError>>()?;
+
+        Ok(Groups {
+            groups: groups
+                .into_iter()
+                .map(|(name, config)| (name, Group::Pending(Box::new(config))))
+                .collect(),
+            materials,
+            textures,
+        })
+    }
+
+    pub fn material(&mut self, material: MaterialRef) -> Result<Arc<Materials>, SceneError> {
+        match material {
+            MaterialRef::Named(name) => match self.materials.get(&name) {
+                Some(material) => Ok(material.clone()),
+                None => Err(SceneError::UnknownMaterial(name)),
+            },
+            MaterialRef::Inline(mut material) => {
+                material.load_textures(&mut self.textures)?;
+                Ok(Arc::new(material))
+            }
+        }
+    }
+
+    pub fn textures_mut(&mut self) -> &mut TextureRegistry {
+        &mut self.textures
+    }
+
+    pub fn into_textures(self) -> TextureRegistry {
+        self.textures
+    }
+
+    pub fn get(&mut self, name: &str) -> Result<Vec<Instance>, SceneError> {
+        match self.groups.get(name) {
+            None => return Err(SceneError::UnknownGroup(name.to_owned())),
+            Some(Group::Building) => return Err(SceneError::RecursiveGroup(name.to_owned())),
+            Some(Group::Built(instances)) => return Ok(instances.clone()),
+            Some(Group::Pending(_)) => {}
+        }
+
+        let Some(Group::Pending(config)) = self.groups.insert(name.to_owned(), Group::Building)
+        else {
+            unreachable!()
+        };
+        let instances = config.build(self)?;
+        self.groups
+            .insert(name.to_owned(), Group::Built(instances.clone()));
+        Ok(instances)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::materials::dielectric::Dielectric;
+    use crate::utils::point::Point;
+
+    fn config(json: &str) -> ObjectConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_placed_groups() {
+        let mut groups = Groups::new(
+            HashMap::from([(
+                "ball".to_owned(),
+                config(r#"{"shape": {"Sphere": {"position": [0, 0, 0], "radius": 1}}}"#),
+            )]),
+            HashMap::from([(
+                "glass".to_owned(),
+                Materials::Dielectric(Dielectric::new(1.5)),
+            )]),
+        )
+        .unwrap();
+
+        let instances = config(
+            r#"{
+                "children": [
+                    {"group": "ball", "transform": [{"Translate": [2, 0, 0]}]},
+                    {"group": "ball", "transform": [{"Translate": [-2, 0, 0]}]}
+                ],
+                "transform": [{"Translate": [0, 0, 1]}],
+                "material": "glass"
+            }"#,
+        )
+        .build(&mut groups)
+        .unwrap();
+
+        assert_eq!(instances.len(), 2);
+        assert!(Arc::ptr_eq(&instances[0].shape, &instances[1].shape));
+        assert_eq!(
+            instances[1].transform.apply_point(&Point::default()),
+            Point::from_xyz(-2., 0., 1.)
+        );
+        assert!(matches!(
+            instances[0].material.as_deref(),
+            Some(Materials::Dielectric(_))
+        ));
+    }
+
+    #[test]
+    fn test_recursive_group() {
+        let mut groups = Groups::new(
+            HashMap::from([(
+                "loop".to_owned(),
+                config(r#"{"children": [{"group": "loop"}]}"#),
+            )]),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            groups.get("loop"),
+            Err(SceneError::RecursiveGroup(name)) if name == "loop"
+        ));
+        assert!(matches!(
+            groups.get("missing"),
+            Err(SceneError::UnknownGroup(name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_material() {
+        let mut groups = Groups::new(HashMap::new(), HashMap::new()).unwrap();
+        let error = config(r#"{"shape": {"Sky": {}}, "material": "steel"}"#)
+            .build(&mut groups)
+            .unwrap_err();
+
+        assert!(matches!(error, SceneError::UnknownMaterial(name) if name == "steel"));
+    }
+}
@@ -1,13 +1,30 @@
 pub mod camera;
+pub mod error;
+pub mod gltf;
+pub mod group;
+pub mod obj;
 pub mod object;
+pub mod textures;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
 
 use palette::{LinSrgb, Srgb};
 
+use crate::utils::aabb::Aabb;
+use crate::utils::bvh::Bvh;
 use crate::utils::hit::{Hit, Hittable};
 use crate::utils::ray::Ray;
 use camera::Camera;
-use object::Object;
+use error::SceneError;
+use group::Groups;
+use object::materials::Materials;
+use object::shapes::Shape;
+use object::{Object, ObjectConfig};
 use serde::{Deserialize, Serialize};
+use textures::TextureRegistry;
 
 serde_with::serde_conv!(
     LinSrgbAsArray,
@@ -22,23 +39,188 @@ serde_with::serde_conv!(
     }
 );
 
-#[serde_with::serde_as]
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(try_from = "SceneConfig")]
 pub struct Scene {
     pub camera: Camera,
     pub objects: Vec<Object>,
+    #[serde(skip_serializing)]
+    pub textures: TextureRegistry,
+
+    #[serde(skip_serializing)]
+    bvh: Bvh,
+    #[serde(skip_serializing)]
+    bounded: Vec<usize>,
+    #[serde(skip_serializing)]
+    unbounded: Vec<usize>,
+    #[serde(skip_serializing)]
+    skies: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct SceneConfig {
+    camera: Camera,
+    #[serde(default)]
+    materials: HashMap<String, Materials>,
+    #[serde(default)]
+    groups: HashMap<String, ObjectConfig>,
+    objects: Vec<ObjectConfig>,
+}
+
+impl TryFrom<SceneConfig> for Scene {
+    type Error = SceneError;
+
+    fn try_from(config: SceneConfig) -> Result<Scene, SceneError> {
+        let mut groups = Groups::new(config.groups, config.materials)?;
+        let mut objects = Vec::new();
+        for object in config.objects {
+            for instance in object.build(&mut groups)? {
+                objects.push(instance.into_object()?);
+            }
+        }
+        Ok(Scene::new(config.camera, objects, groups.into_textures()))
+    }
+}
+
+impl Scene {
+    pub fn load(path: &Path) -> Result<Scene, SceneError> {
+        if let Some("gltf" | "glb") = path.extension().and_then(|extension| extension.to_str()) {
+            return gltf::load(path);
+        }
+
+        let file = File::open(path).map_err(|source| SceneError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        let config: SceneConfig = serde_json::from_reader(BufReader::new(file))
+            .map_err(|error| SceneError::json(path.to_owned(), error))?;
+        Scene::try_from(config)
+    }
+
+    pub fn new(camera: Camera, objects: Vec<Object>, textures: TextureRegistry) -> Scene {
+        let mut boxes = Vec::new();
+        let mut bounded = Vec::new();
+        let mut unbounded = Vec::new();
+        let mut skies = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            let aabb = object.shape.bounding_box();
+            if object.shape.is_sky() {
+                skies.push(i);
+            } else if aabb.is_bounded() {
+                boxes.push(aabb);
+                bounded.push(i);
+            } else {
+                unbounded.push(i);
+            }
+        }
+
+        Scene {
+            camera,
+            objects,
+            textures,
+            bvh: Bvh::new(&boxes),
+            bounded,
+            unbounded,
+            skies,
+        }
+    }
+
+    pub fn bounding_box(&self) -> Aabb {
+        self.bounded
+            .iter()
+            .map(|&i| self.objects[i].shape.bounding_box())
+            .fold(Aabb::empty(), |bounds, aabb| bounds.union(&aabb))
+    }
 }
 
 impl Hittable for Scene {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Hit> {
-        let mut t_max = t_max;
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Hit<'_>> {
         let mut closest_hit = None;
-        for object in self.objects.iter() {
-            if let Some(hit) = object.hit(ray, t_min, t_max) {
+        let mut t_max = t_max;
+        for &i in self.unbounded.iter() {
+            if let Some(hit) = self.objects[i].hit(ray, t_min, t_max) {
                 t_max = hit.t;
                 closest_hit = Some(hit);
             }
         }
-        closest_hit
+
+        self.bvh.hit(ray, t_min, t_max, |i, t_min, t_max| {
+            let hit = self.objects[self.bounded[i]].hit(ray, t_min, t_max)?;
+            let t = hit.t;
+            closest_hit = Some(hit);
+            Some(t)
+        });
+        closest_hit.or_else(|| {
+            self.skies
+                .iter()
+                .find_map(|&i| self.objects[i].hit(ray, t_min, t_max))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::shapes::Shapes;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_hit_behind_sky() {
+        let scene: Scene = serde_json::from_str(
+            r#"{
+                "camera": {
+                    "position": [0, 0, 0], "target": [1, 0, 0], "up": [0, 0, 1],
+                    "field_of_view": 60, "aperture": 0, "exposure": 1,
+                    "screen_width": 4, "screen_height": 4
+                },
+                "objects": [
+                    {"shape": {"Sky": {}}, "material": {"Light": {"power": 1}}},
+                    {"shape": {"Sphere": {"position": [5, 0, 0], "radius": 1}},
+                     "material": {"Diffuse": {"color": {"Uniform": {"color": [1, 1, 1]}}}}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let ray = Ray::new(Point::default(), Point::from_xyz(1., 0., 0.));
+        let hit = scene.hit(ray, 1e-6, f64::INFINITY).unwrap();
+        assert_eq!(hit.t, 4.);
+        assert!(matches!(hit.object.shape, Shapes::Sphere(_)));
+
+        let ray = Ray::new(Point::default(), Point::from_xyz(-1., 0., 0.));
+        let hit = scene.hit(ray, 1e-6, f64::INFINITY).unwrap();
+        assert!(matches!(hit.object.shape, Shapes::Sky(_)));
+    }
+
+    #[test]
+    fn test_hit_plane_and_sky() {
+        let sky = r#"{"shape": {"Sky": {}}, "material": {"Light": {"power": 1}}}"#;
+        let plane = r#"{"shape": {"HalfSpace": {
+            "position": [0, 0, -1], "normal": [0, 0, 1], "u": [1, 0, 0], "v": [0, 1, 0]
+        }}, "material": {"Diffuse": {"color": {"Uniform": {"color": [1, 1, 1]}}}}}"#;
+
+        for objects in [[sky, plane], [plane, sky]] {
+            let scene: Scene = serde_json::from_str(&format!(
+                r#"{{
+                    "camera": {{
+                        "position": [0, 0, 0], "target": [1, 0, 0], "up": [0, 0, 1],
+                        "field_of_view": 60, "aperture": 0, "exposure": 1,
+                        "screen_width": 4, "screen_height": 4
+                    }},
+                    "objects": [{}]
+                }}"#,
+                objects.join(", ")
+            ))
+            .unwrap();
+
+            let ray = Ray::new(Point::default(), Point::from_xyz(0., 0., -1.));
+            let hit = scene.hit(ray, 1e-6, f64::INFINITY).unwrap();
+            assert_eq!(hit.t, 1.);
+            assert!(matches!(hit.object.shape, Shapes::HalfSpace(_)));
+
+            let ray = Ray::new(Point::default(), Point::from_xyz(0., 0., 1.));
+            let hit = scene.hit(ray, 1e-6, f64::INFINITY).unwrap();
+            assert!(matches!(hit.object.shape, Shapes::Sky(_)));
+        }
     }
 }
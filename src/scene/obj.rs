@@ -0,0 +1,212 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use palette::{LinSrgb, Srgb};
+
+use super::error::SceneError;
+use super::object::colors::texture::Texture;
+use super::object::colors::uniform::Uniform;
+use super::object::colors::Colors;
+use super::object::materials::dielectric::Dielectric;
+use super::object::materials::diffuse::Diffuse;
+use super::object::materials::metal::Metal;
+use super::object::materials::Materials;
+use super::object::shapes::mesh::Mesh;
+use super::object::shapes::Shapes;
+use super::object::Object;
+use super::textures::TextureRegistry;
+use crate::utils::point::Point;
+
+const DEFAULT_COLOR: [f32; 3] = [0.8, 0.8, 0.8];
+
+#[derive(Default)]
+struct MeshBuffers {
+    vertices: Vec<Point>,
+    normals: Vec<Point>,
+    uvs: Vec<(f64, f64)>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl MeshBuffers {
+    fn append(&mut self, mesh: &tobj::Mesh) {
+        let offset = self.vertices.len();
+        let count = mesh.positions.len() / 3;
+        let has_normals = mesh.normals.len() == mesh.positions.len();
+        let has_uvs = mesh.texcoords.len() / 2 == count;
+
+        for i in 0..count {
+            let position = &mesh.positions[3 * i..3 * i + 3];
+            self.vertices.push(Point::from_xyz(
+                position[0] as f64,
+                position[1] as f64,
+                position[2] as f64,
+            ));
+            if has_normals {
+                let normal = &mesh.normals[3 * i..3 * i + 3];
+                self.normals.push(Point::from_xyz(
+                    normal[0] as f64,
+                    normal[1] as f64,
+                    normal[2] as f64,
+                ));
+            }
+            if has_uvs {
+                let uv = &mesh.texcoords[2 * i..2 * i + 2];
+                self.uvs.push((uv[0] as f64, 1. - uv[1] as f64));
+            }
+        }
+        self.triangles.extend(
+            mesh.indices
+                .chunks_exact(3)
+                .map(|face| [0, 1, 2].map(|k| offset + face[k] as usize)),
+        );
+    }
+
+    fn into_shape(self) -> Result<Shapes, String> {
+        let normals = if self.normals.len() == self.vertices.len() {
+            self.normals
+        } else {
+            Vec::new()
+        };
+        let uvs = if self.uvs.len() == self.vertices.len() {
+            self.uvs
+        } else {
+            Vec::new()
+        };
+        Ok(Shapes::Mesh(Mesh::new(
+            self.vertices,
+            normals,
+            uvs,
+            self.triangles,
+        )?))
+    }
+}
+
+pub fn load(
+    path: &Path,
+    material: Option<Arc<Materials>>,
+    textures: &mut TextureRegistry,
+) -> Result<Vec<Object>, SceneError> {
+    let error = |message: String| SceneError::Obj {
+        path: path.to_owned(),
+        message,
+    };
+    let (models, materials) = tobj::load_obj(path, &tobj::GPU_LOAD_OPTIONS)
+        .map_err(|source| error(source.to_string()))?;
+
+    if let Some(material) = material {
+        let mut buffers = MeshBuffers::default();
+        for model in models.iter() {
+            buffers.append(&model.mesh);
+        }
+        return Ok(vec![Object {
+            shape: buffers.into_shape().map_err(error)?,
+            material,
+            normal_map: None,
+        }]);
+    }
+
+    let materials = materials.unwrap_or_default();
+    let directory = path.parent().unwrap_or(Path::new(""));
+    models
+        .iter()
+        .filter(|model| !model.mesh.indices.is_empty())
+        .map(|model| {
+            let mut buffers = MeshBuffers::default();
+            buffers.append(&model.mesh);
+            let material = match model.mesh.material_id.and_then(|id| materials.get(id)) {
+                Some(material) => convert_material(material, directory, textures)?,
+                None => Materials::Diffuse(Diffuse {
+                    color: uniform(DEFAULT_COLOR),
+                }),
+            };
+            Ok(Object {
+                shape: buffers.into_shape().map_err(error)?,
+                material: Arc::new(material),
+                normal_map: None,
+            })
+        })
+        .collect()
+}
+
+fn convert_material(
+    material: &tobj::Material,
+    directory: &Path,
+    textures: &mut TextureRegistry,
+) -> Result<Materials, SceneError> {
+    let diffuse = material.diffuse.unwrap_or(DEFAULT_COLOR);
+    let specular = material.specular.unwrap_or_default();
+    let dissolve = material.dissolve.unwrap_or(1.);
+    let illumination = material.illumination_model.unwrap_or(2);
+
+    if dissolve < 1. || matches!(illumination, 4 | 6 | 7) {
+        return Ok(Materials::Dielectric(Dielectric::new(
+            material.optical_density.unwrap_or(1.5) as f64,
+        )));
+    }
+
+    let max = |color: [f32; 3]| color.into_iter().fold(0., f32::max);
+    if illumination == 3 || max(specular) > max(diffuse) {
+        let shininess = material.shininess.unwrap_or(0.) as f64;
+        return Ok(Materials::Metal(Metal {
+            color: uniform(specular),
+            roughness: (2. / (shininess + 2.)).sqrt(),
+        }));
+    }
+
+    let color = match &material.diffuse_texture {
+        Some(texture) => Colors::Texture(Texture::open(&directory.join(texture), textures)?),
+        None => uniform(diffuse),
+    };
+    Ok(Materials::Diffuse(Diffuse { color }))
+}
+
+fn uniform(color: [f32; 3]) -> Colors {
+    let color: LinSrgb = Srgb::new(color[0], color[1], color[2]).into_linear();
+    Colors::Uniform(Uniform { color })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_load() {
+        let directory = std::env::temp_dir().join("raytracer_rust_test_obj");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("quad.mtl"),
+            "newmtl red\nKd 1 0 0\n\nnewmtl glass\nNi 1.3\nd 0.5\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("quad.obj"),
+            "mtllib quad.mtl\n\
+             v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n\
+             o first\nusemtl red\nf 1 2 3\n\
+             o second\nusemtl glass\nf 1 3 4\n",
+        )
+        .unwrap();
+
+        let mut textures = TextureRegistry::default();
+        let objects = load(&directory.join("quad.obj"), None, &mut textures).unwrap();
+        assert_eq!(objects.len(), 2);
+        assert!(matches!(*objects[0].material, Materials::Diffuse(_)));
+        assert!(matches!(
+            *objects[1].material,
+            Materials::Dielectric(Dielectric {
+                refractive_index,
+                ..
+            }) if (refractive_index - 1.3).abs() < 1e-6
+        ));
+
+        let objects = load(
+            &directory.join("quad.obj"),
+            Some(Arc::new(Materials::Dielectric(Dielectric::new(1.5)))),
+            &mut textures,
+        )
+        .unwrap();
+        assert_eq!(objects.len(), 1);
+        assert!(matches!(&objects[0].shape, Shapes::Mesh(mesh) if mesh.len() == 2));
+    }
+}
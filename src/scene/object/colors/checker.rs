@@ -0,0 +1,56 @@
+use super::{Color, Colors, Mapping};
+use crate::utils::hit::ShapeHit;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Checker {
+    pub even: Box<Colors>,
+    pub odd: Box<Colors>,
+    #[serde(default)]
+    pub mapping: Mapping,
+    pub scale: f64,
+}
+
+impl Color for Checker {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        let parity: i64 = self
+            .mapping
+            .coordinates(shape_hit)
+            .iter()
+            .map(|x| (x * self.scale).floor() as i64)
+            .sum();
+
+        if parity.rem_euclid(2) == 0 {
+            self.even.get_color(ray, t, shape_hit)
+        } else {
+            self.odd.get_color(ray, t, shape_hit)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_checker() {
+        let checker = Checker {
+            even: Box::new(Colors::gray(0.)),
+            odd: Box::new(Colors::gray(1.)),
+            mapping: Mapping::Position,
+            scale: 2.,
+        };
+        let color = |position| {
+            let shape_hit = ShapeHit::flat(position, 0., 0.);
+            let ray = Ray::new(Point::default(), Point::from_xyz(0., 0., -1.));
+            checker.get_color(ray, 1., &shape_hit).red
+        };
+
+        assert_eq!(color(Point::from_xyz(0.25, 0.25, 0.)), 0.);
+        assert_eq!(color(Point::from_xyz(0.75, 0.25, 0.)), 1.);
+        assert_eq!(color(Point::from_xyz(-0.25, 0.25, 0.)), 1.);
+    }
+}
@@ -0,0 +1,103 @@
+use super::{Color, Colors};
+use crate::utils::hit::ShapeHit;
+use crate::utils::ray::Ray;
+use palette::{LinSrgb, Mix as _};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Mix {
+    pub first: Box<Colors>,
+    pub second: Box<Colors>,
+    pub factor: Box<Colors>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Multiply {
+    pub first: Box<Colors>,
+    pub second: Box<Colors>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Add {
+    pub first: Box<Colors>,
+    pub second: Box<Colors>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Scale {
+    pub color: Box<Colors>,
+    pub factor: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Invert {
+    pub color: Box<Colors>,
+}
+
+impl Color for Mix {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        let factor = self.factor.get_color(ray, t, shape_hit);
+        let factor = (factor.red + factor.green + factor.blue) / 3.;
+        self.first
+            .get_color(ray, t, shape_hit)
+            .mix(self.second.get_color(ray, t, shape_hit), factor)
+    }
+}
+
+impl Color for Multiply {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        self.first.get_color(ray, t, shape_hit) * self.second.get_color(ray, t, shape_hit)
+    }
+}
+
+impl Color for Add {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        self.first.get_color(ray, t, shape_hit) + self.second.get_color(ray, t, shape_hit)
+    }
+}
+
+impl Color for Scale {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        self.color.get_color(ray, t, shape_hit) * self.factor
+    }
+}
+
+impl Color for Invert {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        LinSrgb::new(1., 1., 1.) - self.color.get_color(ray, t, shape_hit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_combinators() {
+        let color: Colors = serde_json::from_str(
+            r#"{"Mix": {
+                "first": {"Uniform": {"color": [0, 0, 0]}},
+                "second": {"Scale": {"color": {"Uniform": {"color": [1, 1, 1]}}, "factor": 2}},
+                "factor": {"Invert": {"color": {"Add": {
+                    "first": {"Multiply": {
+                        "first": {"Uniform": {"color": [1, 1, 1]}},
+                        "second": {"Gradient": {"input": "U", "ramp": {"stops": [
+                            {"position": 0, "color": [0, 0, 0]},
+                            {"position": 1, "color": [1, 1, 1]}
+                        ]}}}
+                    }},
+                    "second": {"Uniform": {"color": [0, 0, 0]}}
+                }}}}
+            }}"#,
+        )
+        .unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0.75, 0.);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+
+        assert_eq!(
+            color.get_color(ray, 1., &shape_hit),
+            LinSrgb::new(0.5, 0.5, 0.5)
+        );
+    }
+}
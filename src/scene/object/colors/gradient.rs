@@ -0,0 +1,71 @@
+use super::noise::NoiseSource;
+use super::ramp::ColorRamp;
+use super::Color;
+use crate::utils::hit::ShapeHit;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Input {
+    U,
+    V,
+    Height,
+    NormalZ,
+    Distance,
+    Noise(NoiseSource),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Gradient {
+    pub input: Input,
+    pub ramp: ColorRamp,
+}
+
+impl Input {
+    pub fn value(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> f64 {
+        match self {
+            Input::U => shape_hit.u,
+            Input::V => shape_hit.v,
+            Input::Height => shape_hit.position.z(),
+            Input::NormalZ => shape_hit.normal.z(),
+            Input::Distance => t * ray.direction.norm(),
+            Input::Noise(noise) => noise.value(shape_hit),
+        }
+    }
+}
+
+impl Color for Gradient {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        self.ramp.get_color(self.input.value(ray, t, shape_hit))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::colors::Colors;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_gradient() {
+        let ramp = r#"{"stops": [{"position": 0, "color": [0, 0, 0]}, {"position": 2, "color": [1, 1, 1]}]}"#;
+        let gradient: Colors = serde_json::from_str(&format!(
+            r#"{{"Gradient": {{"input": "Height", "ramp": {ramp}}}}}"#
+        ))
+        .unwrap();
+        let noise: Colors = serde_json::from_str(&format!(
+            r#"{{"Noise": {{"pattern": "Marble", "frequency": 2, "octaves": 3, "seed": 1, "ramp": {ramp}}}}}"#
+        ))
+        .unwrap();
+
+        let shape_hit = ShapeHit::flat(Point::from_xyz(0., 0., 1.), 0., 0.);
+        let ray = Ray::new(Point::default(), Point::from_xyz(0., 0., 1.));
+
+        assert_eq!(
+            gradient.get_color(ray, 1., &shape_hit),
+            LinSrgb::new(0.5, 0.5, 0.5)
+        );
+        assert!(noise.get_color(ray, 1., &shape_hit).red <= 0.5);
+    }
+}
@@ -0,0 +1,80 @@
+use super::{Color, Colors, Mapping};
+use crate::utils::hit::ShapeHit;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Grid {
+    pub line: Box<Colors>,
+    pub background: Box<Colors>,
+    #[serde(default)]
+    pub mapping: Mapping,
+    pub scale: f64,
+    pub line_width: f64,
+}
+
+impl Color for Grid {
+    fn get_color(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        let coordinates = self.mapping.coordinates(shape_hit);
+        let axes = match self.mapping {
+            Mapping::Uv => vec![0, 1],
+            Mapping::Position => {
+                let normal = shape_hit.normal.coord.map(f64::abs);
+                let facing = (0..3).max_by(|&i, &j| normal[i].total_cmp(&normal[j]));
+                (0..3).filter(|&axis| Some(axis) != facing).collect()
+            }
+        };
+
+        let half_width = self.line_width / 2.;
+        let is_line = axes.into_iter().any(|axis| {
+            let cell = (coordinates[axis] * self.scale).rem_euclid(1.);
+            cell < half_width || cell > 1. - half_width
+        });
+
+        if is_line {
+            self.line.get_color(ray, t, shape_hit)
+        } else {
+            self.background.get_color(ray, t, shape_hit)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_grid() {
+        let grid = |mapping| Grid {
+            line: Box::new(Colors::gray(1.)),
+            background: Box::new(Colors::gray(0.)),
+            mapping,
+            scale: 2.,
+            line_width: 0.2,
+        };
+        let color = |grid: &Grid, position, normal, (u, v)| {
+            let shape_hit = ShapeHit {
+                normal,
+                ..ShapeHit::flat(position, u, v)
+            };
+            let ray = Ray::new(Point::default(), -normal);
+            grid.get_color(ray, 1., &shape_hit).red
+        };
+        let up = Point::from_xyz(0., 0., 1.);
+        let side = Point::from_xyz(1., 0., 0.);
+
+        let uv = grid(Mapping::Uv);
+        assert_eq!(color(&uv, Point::default(), up, (0.25, 0.25)), 0.);
+        assert_eq!(color(&uv, Point::default(), up, (0.52, 0.25)), 1.);
+        assert_eq!(color(&uv, Point::default(), up, (0.25, 0.98)), 1.);
+
+        let position = grid(Mapping::Position);
+        let at = |x, y, z| Point::from_xyz(x, y, z);
+        assert_eq!(color(&position, at(0.25, 0.25, 0.5), up, (0., 0.)), 0.);
+        assert_eq!(color(&position, at(0.5, 0.25, 0.25), up, (0., 0.)), 1.);
+        assert_eq!(color(&position, at(0.5, 0.25, 0.25), side, (0., 0.)), 0.);
+        assert_eq!(color(&position, at(-0.25, -0.5, 0.), up, (0., 0.)), 1.);
+    }
+}
@@ -0,0 +1,201 @@
+use image::{Rgba, Rgba32FImage};
+use serde::{Deserialize, Serialize};
+
+const MISSING: [f32; 4] = [1., 0., 1., 1.];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Filter {
+    #[default]
+    Nearest,
+    Bilinear,
+    Trilinear,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Wrap {
+    #[default]
+    Repeat,
+    Clamp,
+    Mirror,
+}
+
+#[derive(Debug, Default)]
+pub struct MipMap {
+    levels: Vec<Rgba32FImage>,
+}
+
+impl MipMap {
+    pub fn new(image: Rgba32FImage) -> MipMap {
+        let mut levels = vec![image];
+        loop {
+            let last = levels.last().unwrap();
+            if last.width() <= 1 && last.height() <= 1 {
+                break;
+            }
+            let next = downsample(last);
+            levels.push(next);
+        }
+        MipMap { levels }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.first().is_none_or(|image| image.is_empty())
+    }
+
+    pub fn levels(&self) -> &[Rgba32FImage] {
+        &self.levels
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.levels
+            .iter()
+            .map(|image| std::mem::size_of_val(image.as_raw().as_slice()))
+            .sum()
+    }
+
+    pub fn sample(
+        &self,
+        (u, v): (f64, f64),
+        (du, dv): (f64, f64),
+        filter: Filter,
+        wrap: Wrap,
+    ) -> [f32; 4] {
+        if self.is_empty() {
+            return MISSING;
+        }
+        match filter {
+            Filter::Nearest => nearest(&self.levels[0], u, v, wrap),
+            Filter::Bilinear => bilinear(&self.levels[0], u, v, wrap),
+            Filter::Trilinear => {
+                let (width, height) = self.levels[0].dimensions();
+                let texels = (du * width as f64).max(dv * height as f64);
+                let lod = texels.log2().clamp(0., (self.levels.len() - 1) as f64);
+                if lod.is_nan() || lod <= 0. {
+                    return bilinear(&self.levels[0], u, v, wrap);
+                }
+
+                let level = lod.floor() as usize;
+                let fine = bilinear(&self.levels[level], u, v, wrap);
+                if level + 1 == self.levels.len() {
+                    return fine;
+                }
+                let coarse = bilinear(&self.levels[level + 1], u, v, wrap);
+                lerp(fine, coarse, lod.fract() as f32)
+            }
+        }
+    }
+}
+
+fn downsample(image: &Rgba32FImage) -> Rgba32FImage {
+    let (width, height) = image.dimensions();
+    Rgba32FImage::from_fn((width / 2).max(1), (height / 2).max(1), |x, y| {
+        let mut sum = [0.; 4];
+        for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            let pixel = image.get_pixel((2 * x + dx).min(width - 1), (2 * y + dy).min(height - 1));
+            for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                *total += channel / 4.;
+            }
+        }
+        Rgba(sum)
+    })
+}
+
+fn wrap_index(index: i64, size: u32, wrap: Wrap) -> u32 {
+    let size = size as i64;
+    let index = match wrap {
+        Wrap::Repeat => index.rem_euclid(size),
+        Wrap::Clamp => index.clamp(0, size - 1),
+        Wrap::Mirror => {
+            let index = index.rem_euclid(2 * size);
+            if index < size {
+                index
+            } else {
+                2 * size - 1 - index
+            }
+        }
+    };
+    index as u32
+}
+
+fn texel(image: &Rgba32FImage, x: i64, y: i64, wrap: Wrap) -> [f32; 4] {
+    let (width, height) = image.dimensions();
+    image
+        .get_pixel(wrap_index(x, width, wrap), wrap_index(y, height, wrap))
+        .0
+}
+
+fn nearest(image: &Rgba32FImage, u: f64, v: f64, wrap: Wrap) -> [f32; 4] {
+    let (width, height) = image.dimensions();
+    let x = (u * width as f64).floor() as i64;
+    let y = (v * height as f64).floor() as i64;
+    texel(image, x, y, wrap)
+}
+
+fn bilinear(image: &Rgba32FImage, u: f64, v: f64, wrap: Wrap) -> [f32; 4] {
+    let (width, height) = image.dimensions();
+    let x = u * width as f64 - 0.5;
+    let y = v * height as f64 - 0.5;
+    let (x0, y0) = (x.floor() as i64, y.floor() as i64);
+    let (fx, fy) = ((x - x.floor()) as f32, (y - y.floor()) as f32);
+
+    let top = lerp(
+        texel(image, x0, y0, wrap),
+        texel(image, x0 + 1, y0, wrap),
+        fx,
+    );
+    let bottom = lerp(
+        texel(image, x0, y0 + 1, wrap),
+        texel(image, x0 + 1, y0 + 1, wrap),
+        fx,
+    );
+    lerp(top, bottom, fy)
+}
+
+fn lerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [0, 1, 2, 3].map(|c| a[c] + (b[c] - a[c]) * t)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checker() -> MipMap {
+        MipMap::new(Rgba32FImage::from_fn(2, 2, |x, y| {
+            let value = ((x + y) % 2) as f32;
+            Rgba([value, value, value, 1.])
+        }))
+    }
+
+    #[test]
+    fn test_levels() {
+        let mipmap = checker();
+
+        assert_eq!(mipmap.levels().len(), 2);
+        assert_eq!(mipmap.levels()[1].get_pixel(0, 0).0, [0.5, 0.5, 0.5, 1.]);
+        assert_eq!(mipmap.memory_usage(), 5 * 16);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap_index(-1, 4, Wrap::Repeat), 3);
+        assert_eq!(wrap_index(-1, 4, Wrap::Clamp), 0);
+        assert_eq!(wrap_index(-1, 4, Wrap::Mirror), 0);
+        assert_eq!(wrap_index(5, 4, Wrap::Mirror), 2);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mipmap = checker();
+        let sample = |uv, footprint, filter| mipmap.sample(uv, footprint, filter, Wrap::Repeat)[0];
+
+        assert_eq!(sample((-0.25, 0.25), (0., 0.), Filter::Nearest), 1.);
+        assert_eq!(sample((0.5, 0.5), (0., 0.), Filter::Bilinear), 0.5);
+        assert_eq!(sample((0.25, 0.25), (0., 0.), Filter::Trilinear), 0.);
+        assert_eq!(sample((0.25, 0.25), (4., 4.), Filter::Trilinear), 0.5);
+
+        for filter in [Filter::Nearest, Filter::Bilinear, Filter::Trilinear] {
+            let sample = MipMap::default().sample((0.5, 0.5), (1., 1.), filter, Wrap::Repeat);
+            assert_eq!(sample, MISSING);
+        }
+    }
+}
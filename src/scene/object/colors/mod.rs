@@ -1,9 +1,23 @@
+pub mod checker;
+pub mod combinators;
+pub mod gradient;
+pub mod grid;
+pub mod mipmap;
+pub mod noise;
 pub mod normals;
+pub mod ramp;
 pub mod texture;
 pub mod uniform;
+pub mod uv_transform;
 
+use crate::scene::object::shapes::TOLERANCE;
 use crate::utils::hit::ShapeHit;
 use crate::utils::ray::Ray;
+use checker::Checker;
+use combinators::{Add, Invert, Mix, Multiply, Scale};
+use gradient::Gradient;
+use grid::Grid;
+use noise::Noise;
 use normals::Normals;
 use palette::LinSrgb;
 use serde::{Deserialize, Serialize};
@@ -19,6 +33,41 @@ pub enum Colors {
     Uniform(Uniform),
     Texture(Texture),
     Normals(Normals),
+    Checker(Checker),
+    Grid(Grid),
+    Noise(Noise),
+    Gradient(Gradient),
+    Mix(Mix),
+    Multiply(Multiply),
+    Add(Add),
+    Scale(Scale),
+    Invert(Invert),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Mapping {
+    #[default]
+    Uv,
+    Position,
+}
+
+#[cfg(test)]
+impl Colors {
+    pub fn gray(value: f32) -> Colors {
+        Colors::Uniform(Uniform {
+            color: LinSrgb::new(value, value, value),
+        })
+    }
+}
+
+impl Mapping {
+    pub fn coordinates(&self, shape_hit: &ShapeHit) -> [f64; 3] {
+        match self {
+            Mapping::Uv => [shape_hit.u, shape_hit.v, 0.],
+            // Nudged off integer boundaries so flat faces there don't flicker between cells.
+            Mapping::Position => shape_hit.position.coord.map(|x| x + TOLERANCE),
+        }
+    }
 }
 
 impl Color for Colors {
@@ -28,6 +77,50 @@ impl Color for Colors {
             Colors::Uniform(color) => color.get_color(ray, t, shape_hit),
             Colors::Texture(color) => color.get_color(ray, t, shape_hit),
             Colors::Normals(color) => color.get_color(ray, t, shape_hit),
+            Colors::Checker(color) => color.get_color(ray, t, shape_hit),
+            Colors::Grid(color) => color.get_color(ray, t, shape_hit),
+            Colors::Noise(color) => color.get_color(ray, t, shape_hit),
+            Colors::Gradient(color) => color.get_color(ray, t, shape_hit),
+            Colors::Mix(color) => color.get_color(ray, t, shape_hit),
+            Colors::Multiply(color) => color.get_color(ray, t, shape_hit),
+            Colors::Add(color) => color.get_color(ray, t, shape_hit),
+            Colors::Scale(color) => color.get_color(ray, t, shape_hit),
+            Colors::Invert(color) => color.get_color(ray, t, shape_hit),
+        }
+    }
+}
+
+impl Colors {
+    pub fn textures_mut(&mut self) -> Vec<&mut Texture> {
+        match self {
+            Colors::Texture(texture) => vec![texture],
+            Colors::Checker(checker) => {
+                let mut textures = checker.even.textures_mut();
+                textures.extend(checker.odd.textures_mut());
+                textures
+            }
+            Colors::Grid(grid) => {
+                let mut textures = grid.line.textures_mut();
+                textures.extend(grid.background.textures_mut());
+                textures
+            }
+            Colors::Mix(mix) => {
+                let mut textures = mix.first.textures_mut();
+                textures.extend(mix.second.textures_mut());
+                textures.extend(mix.factor.textures_mut());
+                textures
+            }
+            Colors::Multiply(Multiply { first, second }) | Colors::Add(Add { first, second }) => {
+                let mut textures = first.textures_mut();
+                textures.extend(second.textures_mut());
+                textures
+            }
+            Colors::Scale(Scale { color, .. }) | Colors::Invert(Invert { color }) => {
+                color.textures_mut()
+            }
+            Colors::Uniform(_) | Colors::Normals(_) | Colors::Noise(_) | Colors::Gradient(_) => {
+                Vec::new()
+            }
         }
     }
 }
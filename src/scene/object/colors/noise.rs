@@ -0,0 +1,68 @@
+use std::f64::consts::PI;
+
+use super::ramp::ColorRamp;
+use super::{Color, Mapping};
+use crate::utils::hit::ShapeHit;
+use crate::utils::noise::Perlin;
+use crate::utils::point::Point;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Pattern {
+    Fbm,
+    Turbulence,
+    Marble,
+    Wood,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NoiseSource {
+    pub pattern: Pattern,
+    pub frequency: f64,
+    pub octaves: u32,
+    #[serde(rename = "seed")]
+    pub perlin: Perlin,
+    #[serde(default = "position_mapping")]
+    pub mapping: Mapping,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Noise {
+    #[serde(flatten)]
+    pub source: NoiseSource,
+    pub ramp: ColorRamp,
+}
+
+fn position_mapping() -> Mapping {
+    Mapping::Position
+}
+
+impl NoiseSource {
+    pub fn value(&self, shape_hit: &ShapeHit) -> f64 {
+        let point = self.frequency
+            * Point {
+                coord: self.mapping.coordinates(shape_hit),
+            };
+
+        match self.pattern {
+            Pattern::Fbm => 0.5 + 0.5 * self.perlin.fbm(&point, self.octaves),
+            Pattern::Turbulence => self.perlin.turbulence(&point, self.octaves),
+            Pattern::Marble => {
+                let turbulence = self.perlin.turbulence(&point, self.octaves);
+                0.5 + 0.5 * ((point.x() + 4. * turbulence) * PI).sin()
+            }
+            Pattern::Wood => {
+                let turbulence = self.perlin.turbulence(&point, self.octaves);
+                (point.x().hypot(point.y()) + 0.5 * turbulence).rem_euclid(1.)
+            }
+        }
+    }
+}
+
+impl Color for Noise {
+    fn get_color(&self, _ray: Ray, _t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        self.ramp.get_color(self.source.value(shape_hit))
+    }
+}
@@ -0,0 +1,113 @@
+use palette::{LinSrgb, Mix};
+use serde::{Deserialize, Serialize};
+
+use crate::scene::LinSrgbAsArray;
+
+#[serde_with::serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorStop {
+    pub position: f64,
+    #[serde_as(as = "LinSrgbAsArray")]
+    pub color: LinSrgb,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    Smooth,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ColorRampConfig", into = "ColorRampConfig")]
+pub struct ColorRamp {
+    stops: Vec<ColorStop>,
+    interpolation: Interpolation,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ColorRampConfig {
+    stops: Vec<ColorStop>,
+    #[serde(default)]
+    interpolation: Interpolation,
+}
+
+impl TryFrom<ColorRampConfig> for ColorRamp {
+    type Error = String;
+
+    fn try_from(config: ColorRampConfig) -> Result<ColorRamp, String> {
+        ColorRamp::new(config.stops, config.interpolation)
+    }
+}
+
+impl From<ColorRamp> for ColorRampConfig {
+    fn from(ramp: ColorRamp) -> ColorRampConfig {
+        ColorRampConfig {
+            stops: ramp.stops,
+            interpolation: ramp.interpolation,
+        }
+    }
+}
+
+impl ColorRamp {
+    pub fn new(
+        mut stops: Vec<ColorStop>,
+        interpolation: Interpolation,
+    ) -> Result<ColorRamp, String> {
+        if stops.is_empty() {
+            return Err("color ramp needs at least one stop".to_owned());
+        }
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Ok(ColorRamp {
+            stops,
+            interpolation,
+        })
+    }
+
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    pub fn get_color(&self, position: f64) -> LinSrgb {
+        let next = self.stops.partition_point(|stop| stop.position <= position);
+        if next == 0 {
+            return self.stops[0].color;
+        }
+        if next == self.stops.len() {
+            return self.stops[next - 1].color;
+        }
+
+        let (before, after) = (&self.stops[next - 1], &self.stops[next]);
+        let factor = (position - before.position) / (after.position - before.position);
+        let factor = match self.interpolation {
+            Interpolation::Linear => factor,
+            Interpolation::Smooth => factor * factor * (3. - 2. * factor),
+        };
+        before.color.mix(after.color, factor as f32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_color() {
+        let ramp: ColorRamp = serde_json::from_str(
+            r#"{"stops": [
+                {"position": 1, "color": [1, 1, 1]},
+                {"position": 0, "color": [0, 0, 0]}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(ramp.get_color(-1.), LinSrgb::new(0., 0., 0.));
+        assert_eq!(ramp.get_color(0.25), LinSrgb::new(0.25, 0.25, 0.25));
+        assert_eq!(ramp.get_color(2.), LinSrgb::new(1., 1., 1.));
+        assert!(serde_json::from_str::<ColorRamp>(r#"{"stops": []}"#).is_err());
+
+        let smooth = ColorRamp::new(ramp.stops().to_vec(), Interpolation::Smooth).unwrap();
+        assert_eq!(smooth.get_color(0.5), LinSrgb::new(0.5, 0.5, 0.5));
+        assert!(smooth.get_color(0.25).red < 0.25);
+    }
+}
@@ -1,40 +1,144 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::mipmap::{Filter, MipMap, Wrap};
+use super::uv_transform::UvTransform;
 use super::Color;
+use crate::scene::error::SceneError;
+use crate::scene::textures::TextureRegistry;
 use crate::utils::hit::ShapeHit;
 use crate::utils::ray::Ray;
-use image::io::Reader;
-use image::{DynamicImage, RgbImage};
+use image::{ColorType, DynamicImage, Rgba32FImage};
 use palette::{LinSrgb, Srgb};
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
-
-serde_with::serde_conv!(
-    ImageAsPath,
-    RgbImage,
-    |_image: &RgbImage| { "".to_owned() },
-    |path: String| -> Result<_, std::convert::Infallible> {
-        let image = match Reader::open(path).unwrap().decode().unwrap() {
-            DynamicImage::ImageRgb8(image) => image,
-            _ => panic!("Invalid format"),
-        };
-        Ok(image)
-    }
-);
-
-#[serde_with::serde_as]
+
+const MIN_COSINE: f64 = 0.01;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorSpace {
+    #[default]
+    Srgb,
+    Linear,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Texture {
-    #[serde_as(as = "ImageAsPath")]
-    pub texture: RgbImage,
+    #[serde(rename = "texture")]
+    pub path: PathBuf,
+    #[serde(default)]
+    pub filter: Filter,
+    #[serde(default)]
+    pub wrap: Wrap,
+    #[serde(default)]
+    pub uv: UvTransform,
+    #[serde(default)]
+    pub color_space: ColorSpace,
+    #[serde(skip)]
+    pub image: Arc<MipMap>,
+}
+
+impl Texture {
+    pub fn new(path: PathBuf, image: Arc<MipMap>) -> Texture {
+        Texture {
+            path,
+            filter: Filter::default(),
+            wrap: Wrap::default(),
+            uv: UvTransform::default(),
+            color_space: ColorSpace::default(),
+            image,
+        }
+    }
+
+    pub fn open(path: &Path, textures: &mut TextureRegistry) -> Result<Texture, SceneError> {
+        Ok(Texture::new(
+            path.to_owned(),
+            textures.get(path, ColorSpace::Srgb)?,
+        ))
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        !self.image.is_empty()
+    }
+
+    pub fn load(&mut self, textures: &mut TextureRegistry) -> Result<(), SceneError> {
+        self.image = textures.get(&self.path, self.color_space)?;
+        Ok(())
+    }
+}
+
+pub fn linearize(image: DynamicImage, color_space: ColorSpace) -> Rgba32FImage {
+    let is_linear = color_space == ColorSpace::Linear
+        || matches!(image.color(), ColorType::Rgb32F | ColorType::Rgba32F);
+    let mut image = image.into_rgba32f();
+    if !is_linear {
+        for pixel in image.pixels_mut() {
+            let [red, green, blue, alpha] = pixel.0;
+            let color: LinSrgb = Srgb::new(red, green, blue).into_linear();
+            pixel.0 = [color.red, color.green, color.blue, alpha];
+        }
+    }
+    image
 }
 
 impl Color for Texture {
-    fn get_color(&self, _ray: Ray, _t: f64, shape_hit: &ShapeHit) -> LinSrgb {
-        let (width, height) = self.texture.dimensions();
-        let (u, v) = (shape_hit.u.fract(), shape_hit.v.fract());
-        let color = self
-            .texture
-            .get_pixel((u * width as f64) as u32, (v * height as f64) as u32)
-            .0;
-        Srgb::new(color[0], color[1], color[2]).into_linear()
+    fn get_color(&self, ray: Ray, _t: f64, shape_hit: &ShapeHit) -> LinSrgb {
+        let distance = (shape_hit.position - ray.origin).norm();
+        let cosine = shape_hit
+            .normal
+            .dot(&ray.direction.normalized())
+            .abs()
+            .max(MIN_COSINE);
+        let width = ray.width_at(distance) / cosine;
+        let footprint = (width / shape_hit.dpdu.norm(), width / shape_hit.dpdv.norm());
+
+        let [red, green, blue, _] = self.image.sample(
+            self.uv.apply((shape_hit.u, shape_hit.v)),
+            self.uv.apply_footprint(footprint),
+            self.filter,
+            self.wrap,
+        );
+        LinSrgb::new(red, green, blue)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_linearize() {
+        let gray = DynamicImage::ImageLumaA8(image::GrayAlphaImage::from_pixel(
+            1,
+            1,
+            image::LumaA([188, 128]),
+        ));
+        let [red, green, blue, alpha] = linearize(gray, ColorSpace::Srgb).get_pixel(0, 0).0;
+        assert!((red - 0.5).abs() < 0.01);
+        assert_eq!((red, green), (green, blue));
+        assert!((alpha - 128. / 255.).abs() < 1e-6);
+
+        let float = DynamicImage::ImageRgb32F(image::Rgb32FImage::from_pixel(
+            1,
+            1,
+            image::Rgb([0.25, 2., 0.]),
+        ));
+        assert_eq!(
+            linearize(float, ColorSpace::Srgb).get_pixel(0, 0).0,
+            [0.25, 2., 0., 1.]
+        );
+    }
+
+    #[test]
+    fn test_unloaded() {
+        let texture: Texture = serde_json::from_str(r#"{"texture": "data/skybox.jpg"}"#).unwrap();
+        assert!(!texture.is_loaded());
+
+        let shape_hit = ShapeHit::flat(Point::default(), 0.5, 0.5);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+        assert_eq!(
+            texture.get_color(ray, 1., &shape_hit),
+            LinSrgb::new(1., 0., 1.)
+        );
     }
 }
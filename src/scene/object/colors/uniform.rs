@@ -4,7 +4,6 @@ use crate::utils::hit::ShapeHit;
 use crate::utils::ray::Ray;
 use palette::LinSrgb;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
 
 #[serde_with::serde_as]
 #[derive(Debug, Serialize, Deserialize)]
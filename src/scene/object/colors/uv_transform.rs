@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UvTransform {
+    pub offset: (f64, f64),
+    pub scale: (f64, f64),
+    pub rotation: f64,
+    pub flip_u: bool,
+    pub flip_v: bool,
+}
+
+impl Default for UvTransform {
+    fn default() -> UvTransform {
+        UvTransform {
+            offset: (0., 0.),
+            scale: (1., 1.),
+            rotation: 0.,
+            flip_u: false,
+            flip_v: false,
+        }
+    }
+}
+
+impl UvTransform {
+    pub fn apply(&self, (u, v): (f64, f64)) -> (f64, f64) {
+        let u = if self.flip_u { 1. - u } else { u } * self.scale.0;
+        let v = if self.flip_v { 1. - v } else { v } * self.scale.1;
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        (
+            cos * u - sin * v + self.offset.0,
+            sin * u + cos * v + self.offset.1,
+        )
+    }
+
+    pub fn apply_footprint(&self, (du, dv): (f64, f64)) -> (f64, f64) {
+        let (du, dv) = (du * self.scale.0.abs(), dv * self.scale.1.abs());
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        (cos * du + sin * dv, sin * du + cos * dv)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let transform = UvTransform {
+            offset: (0.5, 0.),
+            scale: (2., 1.),
+            rotation: 90.,
+            flip_u: false,
+            flip_v: true,
+        };
+        let (u, v) = transform.apply((0.25, 0.25));
+
+        assert!((u - -0.25).abs() < 1e-12);
+        assert!((v - 0.5).abs() < 1e-12);
+        assert_eq!(UvTransform::default().apply((0.25, 0.75)), (0.25, 0.75));
+    }
+}
@@ -0,0 +1,52 @@
+use super::conductor::{scatter, Fresnel};
+use super::microfacet::{Frame, Ggx};
+use super::Material;
+use crate::utils::hit::ShapeHit;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Anisotropic {
+    pub fresnel: Fresnel,
+    pub roughness_tangent: f64,
+    pub roughness_bitangent: f64,
+    #[serde(default)]
+    pub rotation: f64,
+}
+
+impl Material for Anisotropic {
+    fn scatter_ray(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>) {
+        let frame = Frame::facing(shape_hit, &-ray.direction).rotated(self.rotation);
+        let ggx = Ggx::anisotropic(self.roughness_tangent, self.roughness_bitangent);
+        scatter(&self.fresnel, ggx, &frame, ray, t, shape_hit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_brushed() {
+        let brushed: Anisotropic = serde_json::from_str(
+            r#"{
+                "fresnel": {"Reflectance": {"Uniform": {"color": [1, 1, 1]}}},
+                "roughness_tangent": 0.8,
+                "roughness_bitangent": 0,
+                "rotation": 90
+            }"#,
+        )
+        .unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+
+        for _ in 0..20 {
+            if let (_, Some(next_ray)) = brushed.scatter_ray(ray, 1., &shape_hit) {
+                assert!(next_ray.direction.x().abs() < 1e-9);
+                assert!(next_ray.direction.z() > 0.);
+            }
+        }
+    }
+}
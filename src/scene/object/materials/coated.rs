@@ -0,0 +1,98 @@
+use super::dielectric::Dielectric;
+use super::{white, Material, Materials};
+use crate::scene::LinSrgbAsArray;
+use crate::utils::hit::ShapeHit;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[serde_with::serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Coated {
+    pub base: Box<Materials>,
+    pub refractive_index: f64,
+    #[serde(default)]
+    pub thickness: f64,
+    #[serde_as(as = "LinSrgbAsArray")]
+    #[serde(default = "white")]
+    pub tint: LinSrgb,
+}
+
+impl Material for Coated {
+    fn scatter_ray(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>) {
+        let direction = ray.direction.normalized();
+        let c = -direction.dot(&shape_hit.normal);
+        if c <= 0. {
+            return self.base.scatter_ray(ray, t, shape_hit);
+        }
+
+        let r = 1. / self.refractive_index;
+        if Dielectric::is_reflected(c, r) {
+            let reflected = direction + (2. * c) * shape_hit.normal;
+            return (white(), Some(ray.scattered(shape_hit.position, reflected)));
+        }
+
+        let (color, next_ray) = self.base.scatter_ray(ray, t, shape_hit);
+        let cos_out = next_ray.map_or(1., |next_ray| {
+            next_ray.direction.normalized().dot(&shape_hit.normal).abs()
+        });
+        (color * self.attenuation(c, cos_out), next_ray)
+    }
+}
+
+impl Coated {
+    fn attenuation(&self, cos_in: f64, cos_out: f64) -> LinSrgb {
+        let r = 1. / self.refractive_index;
+        let refracted = |c: f64| (1. - r * r * (1. - c * c)).max(0.).sqrt();
+        let length = (1. / refracted(cos_in) + 1. / refracted(cos_out)) / 2.;
+        let exponent = (self.thickness * length) as f32;
+        LinSrgb::new(
+            self.tint.red.powf(exponent),
+            self.tint.green.powf(exponent),
+            self.tint.blue.powf(exponent),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::colors::uniform::Uniform;
+    use crate::scene::object::colors::Colors;
+    use crate::scene::object::materials::diffuse::Diffuse;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_coated() {
+        let coated = Coated {
+            base: Box::new(Materials::Diffuse(Diffuse {
+                color: Colors::Uniform(Uniform { color: white() }),
+            })),
+            refractive_index: 1.5,
+            thickness: 2.,
+            tint: LinSrgb::new(0.5, 1., 1.),
+        };
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+
+        let (mut coat, mut base) = (0, 0);
+        for _ in 0..500 {
+            let (color, next_ray) = coated.scatter_ray(ray, 1., &shape_hit);
+            let next_ray = next_ray.unwrap();
+            if color == white() {
+                assert_eq!(next_ray.direction, Point::from_xyz(0., 0., 1.));
+                coat += 1;
+            } else {
+                let cos_out = next_ray.direction.normalized().z();
+                assert!(cos_out > 0.);
+                let sin_inside = (1. - cos_out * cos_out) / (1.5 * 1.5);
+                let length = (1. + 1. / (1. - sin_inside).sqrt()) / 2.;
+                let expected = 0.5_f32.powf((2. * length) as f32);
+                assert!((color.red - expected).abs() < 1e-6);
+                assert_eq!((color.green, color.blue), (1., 1.));
+                base += 1;
+            }
+        }
+        assert!(coat > 0 && base > coat);
+    }
+}
@@ -0,0 +1,109 @@
+use super::fresnel;
+use super::microfacet::{reflect, Frame, Ggx};
+use super::Material;
+use crate::scene::object::colors::texture::Texture;
+use crate::scene::object::colors::{Color, Colors};
+use crate::utils::hit::ShapeHit;
+use crate::utils::point::Point;
+use crate::utils::ray::Ray;
+use palette::LinSrgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Fresnel {
+    Reflectance(Colors),
+    Ior { eta: [f64; 3], k: [f64; 3] },
+}
+
+impl Fresnel {
+    pub fn get(&self, ray: Ray, t: f64, shape_hit: &ShapeHit, cosine: f64) -> LinSrgb {
+        match self {
+            Fresnel::Reflectance(color) => {
+                fresnel::schlick(cosine, color.get_color(ray, t, shape_hit))
+            }
+            Fresnel::Ior { eta, k } => {
+                let [red, green, blue] =
+                    [0, 1, 2].map(|c| fresnel::conductor(cosine, eta[c], k[c]) as f32);
+                LinSrgb::new(red, green, blue)
+            }
+        }
+    }
+
+    pub fn textures_mut(&mut self) -> Vec<&mut Texture> {
+        match self {
+            Fresnel::Reflectance(color) => color.textures_mut(),
+            Fresnel::Ior { .. } => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Conductor {
+    pub fresnel: Fresnel,
+    pub roughness: f64,
+}
+
+impl Material for Conductor {
+    fn scatter_ray(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>) {
+        let frame = Frame::facing(shape_hit, &-ray.direction);
+        scatter(
+            &self.fresnel,
+            Ggx::new(self.roughness),
+            &frame,
+            ray,
+            t,
+            shape_hit,
+        )
+    }
+}
+
+pub fn scatter(
+    fresnel: &Fresnel,
+    ggx: Ggx,
+    frame: &Frame,
+    ray: Ray,
+    t: f64,
+    shape_hit: &ShapeHit,
+) -> (LinSrgb, Option<Ray>) {
+    let wo = frame.to_local(&-ray.direction.normalized());
+    let m = if ggx.is_smooth() {
+        Point::from_xyz(0., 0., 1.)
+    } else {
+        ggx.sample_visible(&wo)
+    };
+    let wi = reflect(&wo, &m);
+    if wo.z() <= 0. || wi.z() <= 0. {
+        return (LinSrgb::new(0., 0., 0.), None);
+    }
+
+    let fresnel = fresnel.get(ray, t, shape_hit, wo.dot(&m));
+    let weight = if ggx.is_smooth() {
+        1.
+    } else {
+        ggx.shadowing_masking(&wo, &wi) / ggx.masking(&wo)
+    };
+    (
+        fresnel * weight as f32,
+        Some(ray.scattered_rough(shape_hit.position, frame.from_local(&wi), ggx.roughness())),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_smooth_conductor() {
+        let conductor: Conductor = serde_json::from_str(
+            r#"{"fresnel": {"Ior": {"eta": [0.2, 0.9, 1.1], "k": [3.9, 2.4, 2.2]}}, "roughness": 0}"#,
+        )
+        .unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+        let ray = Ray::new(Point::from_xyz(-1., 0., 1.), Point::from_xyz(1., 0., -1.));
+
+        let (color, next_ray) = conductor.scatter_ray(ray, 1., &shape_hit);
+        let direction = next_ray.unwrap().direction;
+        assert!((direction - Point::from_xyz(1., 0., 1.).normalized()).norm() < 1e-9);
+        assert!(color.red > color.blue && color.blue < 1. && color.red <= 1.);
+    }
+}
@@ -1,48 +1,270 @@
-use super::Material;
+use super::microfacet::{reflect, Frame, Ggx};
+use super::{white, Material};
+use crate::scene::LinSrgbAsArray;
 use crate::utils::hit::ShapeHit;
+use crate::utils::point::Point;
 use crate::utils::ray::Ray;
+use crate::utils::spectrum::{sample_wavelength, wavelength_to_rgb};
 use palette::LinSrgb;
 use rand::random;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+const REFERENCE_WAVELENGTH: f64 = 587.6;
+const SELLMEIER_TOLERANCE: f64 = 1e-3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "DielectricConfig", into = "DielectricConfig")]
 pub struct Dielectric {
     pub refractive_index: f64,
+    pub roughness: f64,
+    pub absorption: LinSrgb,
+    pub density: f64,
+    pub dispersion: Option<Dispersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Dispersion {
+    Cauchy { b: f64 },
+    Sellmeier { b: [f64; 3], c: [f64; 3] },
+}
+
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct DielectricConfig {
+    #[serde(default)]
+    refractive_index: Option<f64>,
+    #[serde(default)]
+    roughness: f64,
+    #[serde_as(as = "LinSrgbAsArray")]
+    #[serde(default = "white")]
+    absorption: LinSrgb,
+    #[serde(default)]
+    density: f64,
+    #[serde(default)]
+    dispersion: Option<Dispersion>,
+}
+
+impl From<Dielectric> for DielectricConfig {
+    fn from(dielectric: Dielectric) -> DielectricConfig {
+        DielectricConfig {
+            refractive_index: Some(dielectric.refractive_index),
+            roughness: dielectric.roughness,
+            absorption: dielectric.absorption,
+            density: dielectric.density,
+            dispersion: dielectric.dispersion,
+        }
+    }
+}
+
+impl TryFrom<DielectricConfig> for Dielectric {
+    type Error = String;
+
+    fn try_from(config: DielectricConfig) -> Result<Dielectric, String> {
+        let sellmeier = match &config.dispersion {
+            Some(Dispersion::Sellmeier { b, c }) => Some(sellmeier(b, c, REFERENCE_WAVELENGTH)),
+            _ => None,
+        };
+        let refractive_index = match (config.refractive_index, sellmeier) {
+            (Some(given), Some(fitted)) if (given - fitted).abs() > SELLMEIER_TOLERANCE => {
+                return Err(format!(
+                    "refractive index {given} does not match the Sellmeier index {fitted:.4} at {REFERENCE_WAVELENGTH} nm"
+                ))
+            }
+            (Some(refractive_index), _) | (None, Some(refractive_index)) => refractive_index,
+            (None, None) => {
+                return Err(
+                    "dielectric needs a refractive_index unless it has Sellmeier dispersion"
+                        .to_string(),
+                )
+            }
+        };
+        Ok(Dielectric {
+            refractive_index,
+            roughness: config.roughness,
+            absorption: config.absorption,
+            density: config.density,
+            dispersion: config.dispersion,
+        })
+    }
+}
+
+fn sellmeier(b: &[f64; 3], c: &[f64; 3], wavelength: f64) -> f64 {
+    let micrometers2 = (wavelength / 1000.).powi(2);
+    (1. + (0..3)
+        .map(|i| b[i] * micrometers2 / (micrometers2 - c[i]))
+        .sum::<f64>())
+    .sqrt()
 }
 
 impl Material for Dielectric {
-    fn scatter_ray(&self, ray: Ray, _t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>) {
-        let direction = ray.direction.normalized();
+    fn scatter_ray(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>) {
+        let (ray, weight) = match ray.wavelength {
+            None if self.dispersion.is_some() => {
+                let wavelength = sample_wavelength();
+                let ray = Ray {
+                    wavelength: Some(wavelength),
+                    ..ray
+                };
+                (ray, wavelength_to_rgb(wavelength))
+            }
+            _ => (ray, white()),
+        };
 
-        let c = -(direction.dot(&shape_hit.normal));
-        let (c, r) = if c > 0. {
-            (c, 1. / self.refractive_index)
+        let outgoing = -ray.direction.normalized();
+        let frame = Frame::facing(shape_hit, &outgoing);
+        let is_entering = shape_hit.normal.dot(&outgoing) > 0.;
+        let refractive_index = self.refractive_index_at(ray.wavelength);
+        let r = if is_entering {
+            1. / refractive_index
         } else {
-            (-c, self.refractive_index)
+            refractive_index
         };
 
+        let wo = frame.to_local(&outgoing);
+        let ggx = Ggx::new(self.roughness);
+        let m = if self.roughness > 0. {
+            ggx.sample_visible(&wo)
+        } else {
+            Point::from_xyz(0., 0., 1.)
+        };
+
+        let c = wo.dot(&m);
         let delta = 1. - r * r * (1. - c * c);
-        let new_direction = if delta.is_sign_negative() || self.is_reflected(c, r) {
-            direction + (2. * c) * shape_hit.normal
+        let (wi, is_reflection) = if delta.is_sign_negative() || Dielectric::is_reflected(c, r) {
+            (reflect(&wo, &m), true)
         } else {
-            r * direction + (r * c - delta.sqrt()) * shape_hit.normal
+            (-r * wo + (r * c - delta.sqrt()) * m, false)
         };
+        if wo.z() <= 0. || (wi.z() > 0.) != is_reflection {
+            return (LinSrgb::new(0., 0., 0.), None);
+        }
 
+        let mut color = weight
+            * self.attenuation(if is_entering {
+                0.
+            } else {
+                t * ray.direction.norm()
+            });
+        if self.roughness > 0. {
+            color *= (ggx.shadowing_masking(&wo, &wi) / ggx.masking(&wo)) as f32;
+        }
         (
-            LinSrgb::new(1., 1., 1.),
-            Some(Ray {
-                origin: shape_hit.position,
-                direction: new_direction,
-            }),
+            color,
+            Some(ray.scattered_rough(shape_hit.position, frame.from_local(&wi), self.roughness)),
         )
     }
 }
 
 impl Dielectric {
-    fn is_reflected(&self, cos_theta: f64, refractive_ratio: f64) -> bool {
+    pub fn new(refractive_index: f64) -> Dielectric {
+        Dielectric {
+            refractive_index,
+            roughness: 0.,
+            absorption: white(),
+            density: 0.,
+            dispersion: None,
+        }
+    }
+
+    pub fn refractive_index_at(&self, wavelength: Option<f64>) -> f64 {
+        let (Some(dispersion), Some(wavelength)) = (&self.dispersion, wavelength) else {
+            return self.refractive_index;
+        };
+        match dispersion {
+            Dispersion::Cauchy { b } => {
+                let micrometers2 = |wavelength: f64| (wavelength / 1000.).powi(2);
+                self.refractive_index
+                    + b * (1. / micrometers2(wavelength) - 1. / micrometers2(REFERENCE_WAVELENGTH))
+            }
+            Dispersion::Sellmeier { b, c } => sellmeier(b, c, wavelength),
+        }
+    }
+
+    pub fn is_reflected(cos_theta: f64, refractive_ratio: f64) -> bool {
         let r0 = (1. - refractive_ratio) / (1. + refractive_ratio);
         let r0 = r0 * r0;
         let r = r0 + (1. - r0) * (1. - cos_theta).powi(5);
         random::<f64>() < r
     }
+
+    fn attenuation(&self, distance: f64) -> LinSrgb {
+        let exponent = (self.density * distance) as f32;
+        LinSrgb::new(
+            self.absorption.red.powf(exponent),
+            self.absorption.green.powf(exponent),
+            self.absorption.blue.powf(exponent),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn outside() -> Ray {
+        Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.))
+    }
+
+    #[test]
+    fn test_absorption() {
+        let glass = Dielectric {
+            absorption: LinSrgb::new(0.5, 1., 0.),
+            density: 2.,
+            ..Dielectric::new(1.5)
+        };
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+        let inside = Ray::new(Point::from_xyz(0., 0., -1.), Point::from_xyz(0., 0., 0.5));
+
+        let (color, next_ray) = glass.scatter_ray(inside, 2., &shape_hit);
+        assert_eq!(color, LinSrgb::new(0.25, 1., 0.));
+        assert!(next_ray.is_some());
+        assert_eq!(
+            glass.scatter_ray(outside(), 1., &shape_hit).0,
+            LinSrgb::new(1., 1., 1.)
+        );
+    }
+
+    #[test]
+    fn test_rough() {
+        let frosted = Dielectric {
+            roughness: 0.5,
+            ..Dielectric::new(1.5)
+        };
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+
+        for _ in 0..20 {
+            let (color, next_ray) = frosted.scatter_ray(outside(), 1., &shape_hit);
+            assert!(color.red <= 1. + 1e-6);
+            if let Some(next_ray) = next_ray {
+                assert!(next_ray.direction.norm() > 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dispersion() {
+        let bk7 = r#"{"Sellmeier": {
+            "b": [1.03961212, 0.231792344, 1.01046945],
+            "c": [0.00600069867, 0.0200179144, 103.560653]
+        }}"#;
+        let prism: Dielectric =
+            serde_json::from_str(&format!(r#"{{"dispersion": {bk7}}}"#)).unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+
+        assert!((prism.refractive_index - 1.5168).abs() < 1e-4);
+        assert!((prism.refractive_index_at(Some(587.6)) - 1.5168).abs() < 1e-4);
+        assert!(prism.refractive_index_at(Some(450.)) > prism.refractive_index_at(Some(650.)));
+        let (_, next_ray) = prism.scatter_ray(outside(), 1., &shape_hit);
+        assert!(next_ray.unwrap().wavelength.is_some());
+
+        assert!(serde_json::from_str::<Dielectric>(&format!(
+            r#"{{"refractive_index": 1.5168, "dispersion": {bk7}}}"#
+        ))
+        .is_ok());
+        assert!(serde_json::from_str::<Dielectric>(&format!(
+            r#"{{"refractive_index": 1.33, "dispersion": {bk7}}}"#
+        ))
+        .is_err());
+        assert!(serde_json::from_str::<Dielectric>("{}").is_err());
+    }
 }
@@ -17,10 +17,7 @@ impl Material for Diffuse {
 
         (
             self.color.get_color(ray, t, shape_hit),
-            Some(Ray {
-                origin: shape_hit.position,
-                direction,
-            }),
+            Some(ray.scattered_rough(shape_hit.position, direction, 1.)),
         )
     }
 }
@@ -0,0 +1,38 @@
+use palette::LinSrgb;
+
+pub fn schlick(cosine: f64, reflectance: LinSrgb) -> LinSrgb {
+    let weight = (1. - cosine.clamp(0., 1.)).powi(5) as f32;
+    reflectance + (LinSrgb::new(1., 1., 1.) - reflectance) * weight
+}
+
+pub fn conductor(cosine: f64, eta: f64, k: f64) -> f64 {
+    let cos2 = cosine.clamp(0., 1.).powi(2);
+    let sin2 = 1. - cos2;
+    let t0 = eta * eta - k * k - sin2;
+    let a2b2 = (t0 * t0 + 4. * eta * eta * k * k).sqrt();
+    let a = ((a2b2 + t0) / 2.).max(0.).sqrt();
+
+    let t1 = a2b2 + cos2;
+    let t2 = 2. * a * cosine;
+    let perpendicular = (t1 - t2) / (t1 + t2);
+    let t3 = cos2 * a2b2 + sin2 * sin2;
+    let t4 = t2 * sin2;
+    let parallel = perpendicular * (t3 - t4) / (t3 + t4);
+    (perpendicular + parallel) / 2.
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_conductor() {
+        let normal = ((1.5f64 - 1.) / (1.5 + 1.)).powi(2);
+        assert!((conductor(1., 1.5, 0.) - normal).abs() < 1e-9);
+        assert!((conductor(0., 0.2, 3.) - 1.).abs() < 1e-9);
+        assert_eq!(
+            schlick(0., LinSrgb::new(0.5, 0.5, 0.5)),
+            LinSrgb::new(1., 1., 1.)
+        );
+    }
+}
@@ -1,19 +1,123 @@
-use super::Material;
+use super::{white, Material};
+use crate::scene::object::colors::uniform::Uniform;
 use crate::scene::object::colors::{Color, Colors};
 use crate::utils::hit::ShapeHit;
 use crate::utils::ray::Ray;
+use crate::utils::spectrum::{equal_energy_efficacy, upsample, Blackbody};
 use palette::LinSrgb;
 use serde::{Deserialize, Serialize};
 
+fn white_color() -> Colors {
+    Colors::Uniform(Uniform { color: white() })
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PowerUnit {
+    #[default]
+    #[serde(alias = "Relative")]
+    Watts,
+    Lumens,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Light {
+    #[serde(default = "white_color")]
     pub color: Colors,
+    // In watts unless unit is Lumens, which divides by the luminous efficacy.
     pub power: f32,
+    #[serde(default)]
+    pub unit: PowerUnit,
+    #[serde(default, rename = "temperature")]
+    pub blackbody: Option<Blackbody>,
+}
+
+impl Light {
+    pub fn new(color: Colors, power: f32) -> Light {
+        Light {
+            color,
+            power,
+            unit: PowerUnit::default(),
+            blackbody: None,
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self.unit {
+            PowerUnit::Watts => self.power,
+            PowerUnit::Lumens => {
+                let efficacy = match &self.blackbody {
+                    Some(blackbody) => blackbody.efficacy,
+                    None => equal_energy_efficacy(),
+                };
+                self.power / efficacy as f32
+            }
+        }
+    }
+
+    pub fn radiance(&self, ray: Ray, t: f64, shape_hit: &ShapeHit, wavelength: f64) -> f64 {
+        let color = upsample(self.color.get_color(ray, t, shape_hit), wavelength);
+        let blackbody = self
+            .blackbody
+            .as_ref()
+            .map_or(1., |blackbody| blackbody.radiance(wavelength));
+        color * blackbody * self.scale() as f64
+    }
 }
 
 impl Material for Light {
     fn scatter_ray(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>) {
-        let color = self.color.get_color(ray, t, shape_hit) * self.power;
+        let mut color = self.color.get_color(ray, t, shape_hit) * self.scale();
+        if let Some(blackbody) = &self.blackbody {
+            color *= blackbody.color;
+        }
         (color, None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_blackbody_light() {
+        let light: Light =
+            serde_json::from_str(r#"{"temperature": 3200, "power": 1000, "unit": "Lumens"}"#)
+                .unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+
+        let (color, next_ray) = light.scatter_ray(ray, 1., &shape_hit);
+        assert!(next_ray.is_none());
+        assert!(color.red > color.green && color.green > color.blue);
+        let efficacy = light.blackbody.as_ref().unwrap().efficacy as f32;
+        assert!((light.scale() - 1000. / efficacy).abs() < 1e-6);
+        assert!(
+            light.radiance(ray, 1., &shape_hit, 650.) > light.radiance(ray, 1., &shape_hit, 450.)
+        );
+    }
+
+    #[test]
+    fn test_power_units() {
+        let lumens: Light =
+            serde_json::from_str(r#"{"temperature": 3200, "power": 1000, "unit": "Lumens"}"#)
+                .unwrap();
+        let efficacy = lumens.blackbody.as_ref().unwrap().efficacy;
+        let watts: Light = serde_json::from_str(&format!(
+            r#"{{"temperature": 3200, "power": {}, "unit": "Watts"}}"#,
+            1000. / efficacy
+        ))
+        .unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0., 0.);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+
+        assert!((watts.scale() - lumens.scale()).abs() < 1e-4);
+        let (watts_color, _) = watts.scatter_ray(ray, 1., &shape_hit);
+        let (lumens_color, _) = lumens.scatter_ray(ray, 1., &shape_hit);
+        assert!((watts_color.red - lumens_color.red).abs() < 1e-4);
+
+        let relative: Light = serde_json::from_str(r#"{"power": 2, "unit": "Relative"}"#).unwrap();
+        assert_eq!(relative.unit, PowerUnit::Watts);
+        assert_eq!(relative.scale(), 2.);
+    }
+}
@@ -26,10 +26,7 @@ impl Material for Metal {
 
         (
             total_color,
-            Some(Ray {
-                origin: shape_hit.position,
-                direction,
-            }),
+            Some(ray.scattered_rough(shape_hit.position, direction, self.roughness)),
         )
     }
 }
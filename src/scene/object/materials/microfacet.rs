@@ -0,0 +1,152 @@
+use crate::utils::hit::ShapeHit;
+use crate::utils::point::Point;
+use crate::utils::random::get_random_2d;
+use std::f64::consts::{PI, TAU};
+
+const MIN_ALPHA: f64 = 1e-4;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Frame {
+    pub tangent: Point,
+    pub bitangent: Point,
+    pub normal: Point,
+}
+
+impl Frame {
+    pub fn facing(shape_hit: &ShapeHit, direction: &Point) -> Frame {
+        let sign = if shape_hit.normal.dot(direction) < 0. {
+            -1.
+        } else {
+            1.
+        };
+        Frame {
+            tangent: shape_hit.tangent,
+            bitangent: sign * shape_hit.bitangent,
+            normal: sign * shape_hit.normal,
+        }
+    }
+
+    pub fn rotated(&self, angle: f64) -> Frame {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Frame {
+            tangent: cos * self.tangent + sin * self.bitangent,
+            bitangent: cos * self.bitangent - sin * self.tangent,
+            normal: self.normal,
+        }
+    }
+
+    pub fn to_local(&self, vector: &Point) -> Point {
+        Point::from_xyz(
+            vector.dot(&self.tangent),
+            vector.dot(&self.bitangent),
+            vector.dot(&self.normal),
+        )
+    }
+
+    pub fn from_local(&self, vector: &Point) -> Point {
+        vector.x() * self.tangent + vector.y() * self.bitangent + vector.z() * self.normal
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ggx {
+    pub alpha_x: f64,
+    pub alpha_y: f64,
+}
+
+impl Ggx {
+    pub fn new(roughness: f64) -> Ggx {
+        Ggx::anisotropic(roughness, roughness)
+    }
+
+    pub fn anisotropic(roughness_x: f64, roughness_y: f64) -> Ggx {
+        Ggx {
+            alpha_x: (roughness_x * roughness_x).max(MIN_ALPHA),
+            alpha_y: (roughness_y * roughness_y).max(MIN_ALPHA),
+        }
+    }
+
+    pub fn roughness(&self) -> f64 {
+        self.alpha_x.max(self.alpha_y).sqrt()
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        self.alpha_x <= MIN_ALPHA && self.alpha_y <= MIN_ALPHA
+    }
+
+    pub fn distribution(&self, m: &Point) -> f64 {
+        if m.z() <= 0. {
+            return 0.;
+        }
+        let [x, y, z] = [m.x() / self.alpha_x, m.y() / self.alpha_y, m.z()];
+        let denominator = x * x + y * y + z * z;
+        1. / (PI * self.alpha_x * self.alpha_y * denominator * denominator)
+    }
+
+    pub fn lambda(&self, w: &Point) -> f64 {
+        let [x, y, z] = [self.alpha_x * w.x(), self.alpha_y * w.y(), w.z()];
+        ((1. + (x * x + y * y) / (z * z)).sqrt() - 1.) / 2.
+    }
+
+    pub fn masking(&self, w: &Point) -> f64 {
+        1. / (1. + self.lambda(w))
+    }
+
+    pub fn shadowing_masking(&self, wo: &Point, wi: &Point) -> f64 {
+        1. / (1. + self.lambda(wo) + self.lambda(wi))
+    }
+
+    pub fn sample_visible(&self, wo: &Point) -> Point {
+        if self.is_smooth() {
+            return Point::from_xyz(0., 0., 1.);
+        }
+        let view =
+            Point::from_xyz(self.alpha_x * wo.x(), self.alpha_y * wo.y(), wo.z()).normalized();
+        let t1 = if view.z() < 0.9999 {
+            Point::from_xyz(0., 0., 1.).cross(&view).normalized()
+        } else {
+            Point::from_xyz(1., 0., 0.)
+        };
+        let t2 = view.cross(&t1);
+
+        let (u1, u2) = get_random_2d();
+        let (radius, phi) = (u1.sqrt(), TAU * u2);
+        let p1 = radius * phi.cos();
+        let s = 0.5 * (1. + view.z());
+        let p2 = (1. - s) * (1. - p1 * p1).sqrt() + s * radius * phi.sin();
+        let normal = p1 * t1 + p2 * t2 + (1. - p1 * p1 - p2 * p2).max(0.).sqrt() * view;
+
+        let slope = |alpha: f64, x: f64| if alpha <= MIN_ALPHA { 0. } else { alpha * x };
+        Point::from_xyz(
+            slope(self.alpha_x, normal.x()),
+            slope(self.alpha_y, normal.y()),
+            normal.z().max(0.),
+        )
+        .normalized()
+    }
+}
+
+pub fn reflect(w: &Point, m: &Point) -> Point {
+    2. * w.dot(m) * *m - *w
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sample_visible() {
+        let ggx = Ggx::anisotropic(0.5, 0.2);
+        let wo = Point::from_xyz(0.3, -0.2, 0.8).normalized();
+        for _ in 0..100 {
+            let m = ggx.sample_visible(&wo);
+            assert!((m.norm() - 1.).abs() < 1e-9);
+            assert!(m.z() >= 0.);
+            assert!(m.dot(&wo) >= -1e-9);
+        }
+        assert_eq!(ggx.lambda(&Point::from_xyz(0., 0., 1.)), 0.);
+        assert!(
+            (ggx.distribution(&Point::from_xyz(0., 0., 1.)) * PI * 0.25 * 0.04 - 1.).abs() < 1e-9
+        );
+    }
+}
@@ -1,16 +1,34 @@
+pub mod anisotropic;
+pub mod coated;
+pub mod conductor;
 pub mod dielectric;
 pub mod diffuse;
+pub mod fresnel;
 pub mod light;
 pub mod metal;
+pub mod microfacet;
 
+use std::fmt;
+
+use crate::scene::error::SceneError;
+use crate::scene::object::colors::texture::Texture;
+use crate::scene::textures::TextureRegistry;
 use crate::utils::hit::ShapeHit;
 use crate::utils::ray::Ray;
+use anisotropic::Anisotropic;
+use coated::Coated;
+use conductor::Conductor;
 use dielectric::Dielectric;
 use diffuse::Diffuse;
 use light::Light;
 use metal::Metal;
 use palette::LinSrgb;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+pub fn white() -> LinSrgb {
+    LinSrgb::new(1., 1., 1.)
+}
 
 pub trait Material {
     fn scatter_ray(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> (LinSrgb, Option<Ray>);
@@ -22,6 +40,9 @@ pub enum Materials {
     Light(Light),
     Diffuse(Diffuse),
     Dielectric(Dielectric),
+    Conductor(Conductor),
+    Anisotropic(Anisotropic),
+    Coated(Coated),
 }
 
 impl Material for Materials {
@@ -31,6 +52,63 @@ impl Material for Materials {
             Materials::Light(material) => material.scatter_ray(ray, t, shape_hit),
             Materials::Diffuse(material) => material.scatter_ray(ray, t, shape_hit),
             Materials::Dielectric(material) => material.scatter_ray(ray, t, shape_hit),
+            Materials::Conductor(material) => material.scatter_ray(ray, t, shape_hit),
+            Materials::Anisotropic(material) => material.scatter_ray(ray, t, shape_hit),
+            Materials::Coated(material) => material.scatter_ray(ray, t, shape_hit),
+        }
+    }
+}
+
+impl Materials {
+    pub fn textures_mut(&mut self) -> Vec<&mut Texture> {
+        match self {
+            Materials::Metal(material) => material.color.textures_mut(),
+            Materials::Light(material) => material.color.textures_mut(),
+            Materials::Diffuse(material) => material.color.textures_mut(),
+            Materials::Dielectric(_) => Vec::new(),
+            Materials::Conductor(material) => material.fresnel.textures_mut(),
+            Materials::Anisotropic(material) => material.fresnel.textures_mut(),
+            Materials::Coated(material) => material.base.textures_mut(),
+        }
+    }
+
+    pub fn load_textures(&mut self, textures: &mut TextureRegistry) -> Result<(), SceneError> {
+        for texture in self.textures_mut() {
+            if !texture.is_loaded() {
+                texture.load(textures)?;
+            }
         }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum MaterialRef {
+    Named(String),
+    Inline(Materials),
+}
+
+impl<'de> Deserialize<'de> for MaterialRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MaterialRef, D::Error> {
+        struct MaterialRefVisitor;
+
+        impl<'de> Visitor<'de> for MaterialRefVisitor {
+            type Value = MaterialRef;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a material name or an inline material")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<MaterialRef, E> {
+                Ok(MaterialRef::Named(name.to_owned()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<MaterialRef, A::Error> {
+                Materials::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(MaterialRef::Inline)
+            }
+        }
+
+        deserializer.deserialize_any(MaterialRefVisitor)
     }
 }
@@ -1,36 +1,121 @@
 pub mod colors;
 pub mod materials;
+pub mod normal_map;
 pub mod shapes;
 
-use self::materials::{Material, Materials};
-use crate::utils::hit::{Hit, HitInfo, Hittable};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use self::materials::{Material, MaterialRef, Materials};
+use self::normal_map::NormalMap;
+use crate::scene::error::SceneError;
+use crate::scene::group::{Groups, Instance};
+use crate::scene::obj;
+use crate::utils::hit::{Hit, HitInfo, Hittable, Intersection};
 use crate::utils::ray::Ray;
+use crate::utils::transform::Transform;
 use serde::{Deserialize, Serialize};
 use shapes::{Shape, Shapes};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Object {
     pub shape: Shapes,
-    pub material: Materials,
+    pub material: Arc<Materials>,
+    #[serde(default)]
+    pub normal_map: Option<Arc<NormalMap>>,
+}
+
+#[derive(Deserialize)]
+pub struct ObjectConfig {
+    #[serde(default)]
+    shape: Option<Shapes>,
+    #[serde(default)]
+    obj: Option<PathBuf>,
+    #[serde(default)]
+    children: Option<Vec<ObjectConfig>>,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    transform: Option<Transform>,
+    #[serde(default)]
+    material: Option<MaterialRef>,
+    #[serde(default)]
+    normal_map: Option<NormalMap>,
+}
+
+impl ObjectConfig {
+    pub fn build(self, groups: &mut Groups) -> Result<Vec<Instance>, SceneError> {
+        let material = self
+            .material
+            .map(|material| groups.material(material))
+            .transpose()?;
+        let normal_map = self
+            .normal_map
+            .map(|mut normal_map| {
+                normal_map.load_textures(groups.textures_mut())?;
+                Ok::<_, SceneError>(Arc::new(normal_map))
+            })
+            .transpose()?;
+        let instances = match (self.shape, self.obj, self.children, self.group) {
+            (Some(shape), None, None, None) => {
+                vec![Instance::new(shape, material.clone(), normal_map.clone())]
+            }
+            (None, Some(path), None, None) => {
+                obj::load(&path, material.clone(), groups.textures_mut())?
+                    .into_iter()
+                    .map(|object| {
+                        Instance::new(object.shape, Some(object.material), object.normal_map)
+                    })
+                    .collect()
+            }
+            (None, None, Some(children), None) => {
+                let mut instances = Vec::new();
+                for child in children {
+                    instances.extend(child.build(groups)?);
+                }
+                instances
+            }
+            (None, None, None, Some(name)) => groups.get(&name)?,
+            _ => {
+                return Err(SceneError::InvalidObject(
+                    "object needs exactly one of shape, obj, children or group".to_owned(),
+                ))
+            }
+        };
+
+        let transform = self.transform.unwrap_or_default();
+        Ok(instances
+            .iter()
+            .map(|instance| instance.placed(&transform, material.as_ref(), normal_map.as_ref()))
+            .collect())
+    }
 }
 
 impl Hittable for Object {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Hit> {
-        if let Some(t) = self.shape.hit(ray, t_min, t_max) {
-            Some(Hit {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Hit<'_>> {
+        self.shape
+            .hit(ray, t_min, t_max)
+            .map(|Intersection { t, primitive }| Hit {
                 object: self,
                 t,
+                primitive,
                 ray,
             })
-        } else {
-            None
-        }
     }
 }
 
 impl<'object> Object {
     pub fn get_hit_info(&self, hit: Hit<'object>) -> HitInfo<'object> {
-        let shape_hit = self.shape.get_hit_info(hit.ray, hit.t);
+        let mut shape_hit = self.shape.get_hit_info(
+            hit.ray,
+            Intersection {
+                t: hit.t,
+                primitive: hit.primitive,
+            },
+        );
+        if let Some(normal_map) = &self.normal_map {
+            shape_hit = normal_map.apply(hit.ray, hit.t, &shape_hit);
+        }
         let (color, next_ray) = self.material.scatter_ray(hit.ray, hit.t, &shape_hit);
 
         HitInfo {
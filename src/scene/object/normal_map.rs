@@ -0,0 +1,127 @@
+use crate::scene::error::SceneError;
+use crate::scene::object::colors::texture::{ColorSpace, Texture};
+use crate::scene::object::colors::{Color, Colors};
+use crate::scene::object::shapes::TOLERANCE;
+use crate::scene::textures::TextureRegistry;
+use crate::utils::hit::{tangent_frame, ShapeHit};
+use crate::utils::point::Point;
+use crate::utils::ray::Ray;
+use serde::{Deserialize, Serialize};
+
+const BUMP_DELTA: f64 = 1e-3;
+
+fn one() -> f64 {
+    1.
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum NormalMap {
+    Normal {
+        #[serde(flatten)]
+        texture: Texture,
+        #[serde(default = "one")]
+        strength: f64,
+    },
+    Bump {
+        height: Colors,
+        strength: f64,
+    },
+}
+
+impl NormalMap {
+    pub fn load_textures(&mut self, textures: &mut TextureRegistry) -> Result<(), SceneError> {
+        let textures_mut = match self {
+            NormalMap::Normal { texture, .. } => {
+                texture.color_space = ColorSpace::Linear;
+                vec![texture]
+            }
+            NormalMap::Bump { height, .. } => height.textures_mut(),
+        };
+        for texture in textures_mut {
+            if !texture.is_loaded() {
+                texture.load(textures)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, ray: Ray, t: f64, shape_hit: &ShapeHit) -> ShapeHit {
+        let normal = match self {
+            NormalMap::Normal { texture, strength } => {
+                let color = texture.get_color(ray, t, shape_hit);
+                let [x, y, z] = [color.red, color.green, color.blue].map(|c| 2. * c as f64 - 1.);
+                *strength * (x * shape_hit.tangent - y * shape_hit.bitangent) + z * shape_hit.normal
+            }
+            NormalMap::Bump { height, strength } => {
+                let sample = |shape_hit: &ShapeHit| {
+                    let color = height.get_color(ray, t, shape_hit);
+                    (color.red + color.green + color.blue) as f64 / 3.
+                };
+                let base = sample(shape_hit);
+                let slope = |dpdx: Point, du: f64, dv: f64| {
+                    let length = dpdx.norm();
+                    if length < TOLERANCE {
+                        return 0.;
+                    }
+                    let step = BUMP_DELTA / length;
+                    let offset = ShapeHit {
+                        position: shape_hit.position + step * dpdx,
+                        u: shape_hit.u + step * du,
+                        v: shape_hit.v + step * dv,
+                        ..*shape_hit
+                    };
+                    (sample(&offset) - base) / BUMP_DELTA
+                };
+                let slope_u = slope(shape_hit.dpdu, 1., 0.);
+                let slope_v = slope(shape_hit.dpdv, 0., 1.);
+                shape_hit.normal
+                    - *strength * (slope_u * shape_hit.tangent + slope_v * shape_hit.bitangent)
+            }
+        };
+        if normal.norm() < TOLERANCE {
+            return *shape_hit;
+        }
+
+        let normal = normal.normalized();
+        let (tangent, bitangent) = tangent_frame(&normal, &shape_hit.tangent, &shape_hit.bitangent);
+        ShapeHit {
+            normal,
+            tangent,
+            bitangent,
+            ..*shape_hit
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::colors::mipmap::Filter;
+
+    #[test]
+    fn test_bump() {
+        let bump: NormalMap = serde_json::from_str(
+            r#"{"Bump": {"strength": 1, "height": {"Gradient": {"input": "U", "ramp": {"stops": [
+                {"position": 0, "color": [0, 0, 0]},
+                {"position": 1, "color": [1, 1, 1]}
+            ]}}}}}"#,
+        )
+        .unwrap();
+        let shape_hit = ShapeHit::flat(Point::default(), 0.5, 0.5);
+        let ray = Ray::new(Point::from_xyz(0., 0., 1.), Point::from_xyz(0., 0., -1.));
+
+        let bumped = bump.apply(ray, 1., &shape_hit);
+        let expected = Point::from_xyz(-1., 0., 1.).normalized();
+        assert!((bumped.normal - expected).norm() < 1e-3);
+        assert!(bumped.tangent.dot(&bumped.normal).abs() < 1e-9);
+        assert!(bumped.bitangent.dot(&shape_hit.bitangent) > 0.);
+
+        let normal: NormalMap =
+            serde_json::from_str(r#"{"Normal": {"texture": "normal.png", "filter": "Bilinear"}}"#)
+                .unwrap();
+        assert!(matches!(
+            normal,
+            NormalMap::Normal { texture, strength } if texture.filter == Filter::Bilinear && strength == 1.
+        ));
+    }
+}
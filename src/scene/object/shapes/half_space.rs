@@ -1,4 +1,6 @@
 use super::{Shape, ShapeHit, TOLERANCE};
+use crate::utils::aabb::Aabb;
+use crate::utils::hit::{tangent_frame, Intersection};
 use crate::utils::point::{Point, PointAsArray};
 use crate::utils::ray::Ray;
 use serde::{Deserialize, Serialize};
@@ -17,7 +19,7 @@ pub struct HalfSpace {
 }
 
 impl Shape for HalfSpace {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<f64> {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection> {
         let d = self.normal.dot(&ray.direction);
         if d.abs() < TOLERANCE {
             return None;
@@ -27,20 +29,37 @@ impl Shape for HalfSpace {
         let t = n / d;
 
         if t_min < t && t < t_max {
-            Some(t)
+            Some(Intersection::new(t))
         } else {
             None
         }
     }
 
-    fn get_hit_info(&self, ray: Ray, t: f64) -> ShapeHit {
+    fn get_hit_info(&self, ray: Ray, Intersection { t, .. }: Intersection) -> ShapeHit {
         let position = ray.at_t(t);
+        let (uu, uv, vv) = (
+            self.u.dot(&self.u),
+            self.u.dot(&self.v),
+            self.v.dot(&self.v),
+        );
+        let determinant = uu * vv - uv * uv;
+        let dpdu = (vv * self.u - uv * self.v) / determinant;
+        let dpdv = (uu * self.v - uv * self.u) / determinant;
+        let (tangent, bitangent) = tangent_frame(&self.normal, &dpdu, &dpdv);
 
         ShapeHit {
             position,
             normal: self.normal,
+            tangent,
+            bitangent,
             u: self.u.dot(&position),
             v: self.v.dot(&position),
+            dpdu,
+            dpdv,
         }
     }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::unbounded()
+    }
 }
@@ -0,0 +1,190 @@
+use super::triangle;
+use super::{Shape, ShapeHit};
+use crate::utils::aabb::Aabb;
+use crate::utils::bvh::Bvh;
+use crate::utils::hit::Intersection;
+use crate::utils::point::{Point, PointAsArray};
+use crate::utils::ray::Ray;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "MeshConfig", into = "MeshConfig")]
+pub struct Mesh {
+    vertices: Vec<Point>,
+    normals: Vec<Point>,
+    uvs: Vec<(f64, f64)>,
+    triangles: Vec<[usize; 3]>,
+
+    bounds: Aabb,
+    bvh: Bvh,
+}
+
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct MeshConfig {
+    #[serde_as(as = "Vec<PointAsArray>")]
+    vertices: Vec<Point>,
+    #[serde_as(as = "Vec<PointAsArray>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    normals: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    uvs: Vec<(f64, f64)>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl From<Mesh> for MeshConfig {
+    fn from(mesh: Mesh) -> MeshConfig {
+        MeshConfig {
+            vertices: mesh.vertices,
+            normals: mesh.normals,
+            uvs: mesh.uvs,
+            triangles: mesh.triangles,
+        }
+    }
+}
+
+impl TryFrom<MeshConfig> for Mesh {
+    type Error = String;
+
+    fn try_from(config: MeshConfig) -> Result<Mesh, String> {
+        Mesh::new(
+            config.vertices,
+            config.normals,
+            config.uvs,
+            config.triangles,
+        )
+    }
+}
+
+impl Mesh {
+    pub fn new(
+        vertices: Vec<Point>,
+        normals: Vec<Point>,
+        uvs: Vec<(f64, f64)>,
+        triangles: Vec<[usize; 3]>,
+    ) -> Result<Mesh, String> {
+        if !normals.is_empty() && normals.len() != vertices.len() {
+            return Err(format!(
+                "mesh has {} normals for {} vertices",
+                normals.len(),
+                vertices.len()
+            ));
+        }
+        if !uvs.is_empty() && uvs.len() != vertices.len() {
+            return Err(format!(
+                "mesh has {} uvs for {} vertices",
+                uvs.len(),
+                vertices.len()
+            ));
+        }
+        if let Some(index) = triangles.iter().flatten().find(|&&i| i >= vertices.len()) {
+            return Err(format!(
+                "mesh triangle references vertex {index} but mesh has {} vertices",
+                vertices.len()
+            ));
+        }
+
+        let mut mesh = Mesh {
+            vertices,
+            normals,
+            uvs,
+            triangles,
+            bounds: Aabb::empty(),
+            bvh: Bvh::default(),
+        };
+        let boxes: Vec<Aabb> = (0..mesh.triangles.len())
+            .map(|i| triangle::bounding_box(&mesh.triangle_vertices(i)))
+            .collect();
+        mesh.bounds = boxes
+            .iter()
+            .fold(Aabb::empty(), |bounds, aabb| bounds.union(aabb));
+        mesh.bvh = Bvh::new(&boxes);
+
+        Ok(mesh)
+    }
+
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    fn triangle_vertices(&self, i: usize) -> [Point; 3] {
+        self.triangles[i].map(|vertex| self.vertices[vertex])
+    }
+}
+
+impl Shape for Mesh {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection> {
+        let mut primitive = 0;
+        let t = self.bvh.hit(ray, t_min, t_max, |i, t_min, t_max| {
+            let (t, _) = triangle::intersect(ray, &self.triangle_vertices(i), t_min, t_max)?;
+            primitive = i;
+            Some(t)
+        })?;
+        Some(Intersection { t, primitive })
+    }
+
+    fn get_hit_info(&self, ray: Ray, Intersection { t, primitive }: Intersection) -> ShapeHit {
+        let position = ray.at_t(t);
+        let vertices = self.triangle_vertices(primitive);
+        let barycentric = triangle::barycentric(&position, &vertices);
+        let indices = self.triangles[primitive];
+
+        let normals = (!self.normals.is_empty()).then(|| indices.map(|i| self.normals[i]));
+        let uvs = (!self.uvs.is_empty()).then(|| indices.map(|i| self.uvs[i]));
+        triangle::interpolate(
+            position,
+            barycentric,
+            &vertices,
+            normals.as_ref(),
+            uvs.as_ref(),
+        )
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bounds
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn quad() -> Mesh {
+        Mesh::new(
+            vec![
+                Point::from_xyz(0., 0., 1.),
+                Point::from_xyz(1., 0., 1.),
+                Point::from_xyz(1., 1., 1.),
+                Point::from_xyz(0., 1., 1.),
+            ],
+            vec![],
+            vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)],
+            vec![[0, 1, 2], [0, 2, 3]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_invalid_index() {
+        assert!(Mesh::new(vec![Point::default()], vec![], vec![], vec![[0, 0, 1]]).is_err());
+    }
+
+    #[test]
+    fn test_hit() {
+        let mesh = quad();
+        let ray = Ray::new(Point::from_xyz(0.25, 0.75, 0.), Point::from_xyz(0., 0., 2.));
+
+        let intersection = mesh.hit(ray, 0., f64::INFINITY).unwrap();
+        assert_eq!(intersection.t, 0.5);
+        assert_eq!(intersection.primitive, 1);
+
+        let shape_hit = mesh.get_hit_info(ray, intersection);
+        assert_eq!(shape_hit.position, Point::from_xyz(0.25, 0.75, 1.));
+        assert_eq!(shape_hit.normal, Point::from_xyz(0., 0., 1.));
+        assert_eq!((shape_hit.u, shape_hit.v), (0.25, 0.75));
+    }
+}
@@ -1,20 +1,29 @@
 pub mod half_space;
+pub mod mesh;
 pub mod sky;
 pub mod sphere;
+pub mod transformed;
+pub mod triangle;
 
-use crate::utils::hit::ShapeHit;
+use crate::utils::aabb::Aabb;
+use crate::utils::hit::{Intersection, ShapeHit};
 use crate::utils::ray::Ray;
 use half_space::HalfSpace;
+use mesh::Mesh;
 use serde::{Deserialize, Serialize};
 use sky::Sky;
 use sphere::Sphere;
+use transformed::Transformed;
+use triangle::Triangle;
 
 pub const TOLERANCE: f64 = 1e-6;
 
 pub trait Shape {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<f64>;
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection>;
 
-    fn get_hit_info(&self, ray: Ray, t: f64) -> ShapeHit;
+    fn get_hit_info(&self, ray: Ray, intersection: Intersection) -> ShapeHit;
+
+    fn bounding_box(&self) -> Aabb;
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,22 +31,52 @@ pub enum Shapes {
     Sphere(Sphere),
     Sky(Sky),
     HalfSpace(HalfSpace),
+    Triangle(Triangle),
+    Mesh(Mesh),
+    Transformed(Transformed),
+}
+
+impl Shapes {
+    pub fn is_sky(&self) -> bool {
+        match self {
+            Shapes::Sky(_) => true,
+            Shapes::Transformed(shape) => shape.shape.is_sky(),
+            _ => false,
+        }
+    }
 }
 
 impl Shape for Shapes {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<f64> {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection> {
         match self {
             Shapes::Sphere(shape) => shape.hit(ray, t_min, t_max),
             Shapes::Sky(shape) => shape.hit(ray, t_min, t_max),
             Shapes::HalfSpace(shape) => shape.hit(ray, t_min, t_max),
+            Shapes::Triangle(shape) => shape.hit(ray, t_min, t_max),
+            Shapes::Mesh(shape) => shape.hit(ray, t_min, t_max),
+            Shapes::Transformed(shape) => shape.hit(ray, t_min, t_max),
+        }
+    }
+
+    fn get_hit_info(&self, ray: Ray, intersection: Intersection) -> ShapeHit {
+        match self {
+            Shapes::Sphere(shape) => shape.get_hit_info(ray, intersection),
+            Shapes::Sky(shape) => shape.get_hit_info(ray, intersection),
+            Shapes::HalfSpace(shape) => shape.get_hit_info(ray, intersection),
+            Shapes::Triangle(shape) => shape.get_hit_info(ray, intersection),
+            Shapes::Mesh(shape) => shape.get_hit_info(ray, intersection),
+            Shapes::Transformed(shape) => shape.get_hit_info(ray, intersection),
         }
     }
 
-    fn get_hit_info(&self, ray: Ray, t: f64) -> ShapeHit {
+    fn bounding_box(&self) -> Aabb {
         match self {
-            Shapes::Sphere(shape) => shape.get_hit_info(ray, t),
-            Shapes::Sky(shape) => shape.get_hit_info(ray, t),
-            Shapes::HalfSpace(shape) => shape.get_hit_info(ray, t),
+            Shapes::Sphere(shape) => shape.bounding_box(),
+            Shapes::Sky(shape) => shape.bounding_box(),
+            Shapes::HalfSpace(shape) => shape.bounding_box(),
+            Shapes::Triangle(shape) => shape.bounding_box(),
+            Shapes::Mesh(shape) => shape.bounding_box(),
+            Shapes::Transformed(shape) => shape.bounding_box(),
         }
     }
 }
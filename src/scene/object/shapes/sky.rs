@@ -1,26 +1,44 @@
+use super::sphere::spherical_derivatives;
 use super::{Shape, ShapeHit};
+use crate::utils::aabb::Aabb;
+use crate::utils::hit::{tangent_frame, Intersection};
 use crate::utils::ray::Ray;
 use serde::{Deserialize, Serialize};
 use std::f64::consts::{PI, TAU};
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Sky {}
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Sky {
+    #[serde(default)]
+    pub rotation: f64,
+}
 
 impl Shape for Sky {
-    fn hit(&self, _ray: Ray, _t_min: f64, t_max: f64) -> Option<f64> {
-        Some(t_max)
+    fn hit(&self, _ray: Ray, _t_min: f64, t_max: f64) -> Option<Intersection> {
+        Some(Intersection::new(t_max))
     }
 
-    fn get_hit_info(&self, ray: Ray, _t: f64) -> ShapeHit {
-        let normal = -ray.direction.normalized();
-        let u = 0.5 - normal.y().atan2(normal.x()) / TAU;
+    fn get_hit_info(&self, ray: Ray, _: Intersection) -> ShapeHit {
+        let direction = ray.direction.normalized();
+        let normal = -direction;
+        let u = 0.5 - normal.y().atan2(normal.x()) / TAU - self.rotation / 360.;
         let v = 0.5 + normal.z().asin() / PI;
+        let (dpdu, dpdv) = spherical_derivatives(&direction);
+        let dpdu = -dpdu;
+        let (tangent, bitangent) = tangent_frame(&normal, &dpdu, &dpdv);
 
         ShapeHit {
-            position: ray.origin,
+            position: ray.origin + direction,
             normal,
+            tangent,
+            bitangent,
             u,
             v,
+            dpdu,
+            dpdv,
         }
     }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::unbounded()
+    }
 }
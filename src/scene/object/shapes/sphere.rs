@@ -1,4 +1,6 @@
 use super::{Shape, ShapeHit, TOLERANCE};
+use crate::utils::aabb::Aabb;
+use crate::utils::hit::{tangent_frame, Intersection};
 use crate::utils::point::{Point, PointAsArray};
 use crate::utils::ray::Ray;
 use serde::{Deserialize, Serialize};
@@ -13,7 +15,7 @@ pub struct Sphere {
 }
 
 impl Shape for Sphere {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<f64> {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection> {
         let center_origin = self.position - ray.origin;
         let direction = ray.direction;
 
@@ -31,21 +33,48 @@ impl Shape for Sphere {
         [m - d, m + d]
             .iter()
             .copied()
-            .filter(|&t| t_min < t && t < t_max)
-            .next()
+            .find(|&t| t_min < t && t < t_max)
+            .map(Intersection::new)
     }
 
-    fn get_hit_info(&self, ray: Ray, t: f64) -> ShapeHit {
+    fn get_hit_info(&self, ray: Ray, Intersection { t, .. }: Intersection) -> ShapeHit {
         let position = ray.at_t(t);
         let normal = (position - self.position).normalized();
         let u = 0.5 + normal.y().atan2(normal.x()) / TAU;
         let v = 0.5 - normal.z().asin() / PI;
+        let (dpdu, dpdv) = spherical_derivatives(&normal);
+        let (dpdu, dpdv) = (self.radius * dpdu, self.radius * dpdv);
+        let (tangent, bitangent) = tangent_frame(&normal, &dpdu, &dpdv);
 
         ShapeHit {
             position,
             normal,
+            tangent,
+            bitangent,
             u,
             v,
+            dpdu,
+            dpdv,
         }
     }
+
+    fn bounding_box(&self) -> Aabb {
+        let radius = Point::from_xyz(self.radius, self.radius, self.radius);
+        Aabb::new(self.position - radius, self.position + radius)
+    }
+}
+
+pub fn spherical_derivatives(direction: &Point) -> (Point, Point) {
+    let [x, y, z] = direction.coord;
+    let rho = x.hypot(y);
+    let (cos_phi, sin_phi) = if rho > TOLERANCE {
+        (x / rho, y / rho)
+    } else {
+        (1., 0.)
+    };
+
+    (
+        TAU * Point::from_xyz(-y, x, 0.),
+        PI * Point::from_xyz(z * cos_phi, z * sin_phi, -rho),
+    )
 }
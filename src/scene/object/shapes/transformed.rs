@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use super::{Shape, ShapeHit, Shapes};
+use crate::utils::aabb::Aabb;
+use crate::utils::hit::{tangent_frame, Intersection};
+use crate::utils::ray::Ray;
+use crate::utils::transform::Transform;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transformed {
+    pub transform: Transform,
+    pub shape: Arc<Shapes>,
+}
+
+impl Shape for Transformed {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection> {
+        let local_ray = self.transform.inverse().apply_ray(&ray);
+        self.shape.hit(local_ray, t_min, t_max)
+    }
+
+    fn get_hit_info(&self, ray: Ray, intersection: Intersection) -> ShapeHit {
+        let local_ray = self.transform.inverse().apply_ray(&ray);
+        let shape_hit = self.shape.get_hit_info(local_ray, intersection);
+        let normal = self.transform.apply_normal(&shape_hit.normal);
+        let dpdu = self.transform.apply_vector(&shape_hit.dpdu);
+        let dpdv = self.transform.apply_vector(&shape_hit.dpdv);
+        let (tangent, bitangent) = tangent_frame(&normal, &dpdu, &dpdv);
+
+        ShapeHit {
+            position: ray.at_t(intersection.t),
+            normal,
+            tangent,
+            bitangent,
+            dpdu,
+            dpdv,
+            ..shape_hit
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.transform.apply_aabb(&self.shape.bounding_box())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::object::shapes::sphere::Sphere;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn test_ellipsoid() {
+        let ellipsoid = Transformed {
+            transform: Transform::translation(Point::from_xyz(5., 0., 0.))
+                * Transform::scaling(Point::from_xyz(2., 1., 1.)).unwrap(),
+            shape: Arc::new(Shapes::Sphere(Sphere {
+                position: Point::default(),
+                radius: 1.,
+            })),
+        };
+        let ray = Ray::new(Point::default(), Point::from_xyz(1., 0., 0.));
+
+        let intersection = ellipsoid.hit(ray, 0., f64::INFINITY).unwrap();
+        assert_eq!(intersection.t, 3.);
+
+        let shape_hit = ellipsoid.get_hit_info(ray, intersection);
+        assert_eq!(shape_hit.position, Point::from_xyz(3., 0., 0.));
+        assert_eq!(shape_hit.normal, Point::from_xyz(-1., 0., 0.));
+        assert_eq!(
+            ellipsoid.bounding_box(),
+            Aabb::new(Point::from_xyz(3., -1., -1.), Point::from_xyz(7., 1., 1.))
+        );
+    }
+}
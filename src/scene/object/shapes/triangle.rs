@@ -0,0 +1,239 @@
+use super::{Shape, ShapeHit, TOLERANCE};
+use crate::utils::aabb::Aabb;
+use crate::utils::hit::{tangent_frame, Intersection};
+use crate::utils::point::{Point, PointAsArray};
+use crate::utils::ray::Ray;
+use serde::{Deserialize, Serialize};
+
+#[serde_with::serde_as]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Triangle {
+    #[serde_as(as = "[PointAsArray; 3]")]
+    pub vertices: [Point; 3],
+    #[serde_as(as = "Option<[PointAsArray; 3]>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normals: Option<[Point; 3]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uvs: Option<[(f64, f64); 3]>,
+}
+
+impl Shape for Triangle {
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Intersection> {
+        intersect(ray, &self.vertices, t_min, t_max).map(|(t, _)| Intersection::new(t))
+    }
+
+    fn get_hit_info(&self, ray: Ray, Intersection { t, .. }: Intersection) -> ShapeHit {
+        let position = ray.at_t(t);
+        let barycentric = barycentric(&position, &self.vertices);
+
+        interpolate(
+            position,
+            barycentric,
+            &self.vertices,
+            self.normals.as_ref(),
+            self.uvs.as_ref(),
+        )
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        bounding_box(&self.vertices)
+    }
+}
+
+pub fn bounding_box(vertices: &[Point; 3]) -> Aabb {
+    vertices
+        .iter()
+        .fold(Aabb::empty(), |bounds, vertex| bounds.grow(vertex))
+}
+
+pub fn geometric_normal(vertices: &[Point; 3]) -> Point {
+    let [a, b, c] = *vertices;
+    (b - a).cross(&(c - a)).normalized()
+}
+
+pub fn intersect(
+    ray: Ray,
+    vertices: &[Point; 3],
+    t_min: f64,
+    t_max: f64,
+) -> Option<(f64, [f64; 3])> {
+    let direction = ray.direction.coord;
+    let kz = (0..3)
+        .max_by(|&i, &j| direction[i].abs().total_cmp(&direction[j].abs()))
+        .unwrap();
+    let (mut kx, mut ky) = ((kz + 1) % 3, (kz + 2) % 3);
+    if direction[kz] < 0. {
+        std::mem::swap(&mut kx, &mut ky);
+    }
+
+    let shear_x = direction[kx] / direction[kz];
+    let shear_y = direction[ky] / direction[kz];
+    let shear_z = 1. / direction[kz];
+
+    let [a, b, c] = vertices.map(|vertex| (vertex - ray.origin).coord);
+    let project = |p: [f64; 3]| (p[kx] - shear_x * p[kz], p[ky] - shear_y * p[kz]);
+    let ((ax, ay), (bx, by), (cx, cy)) = (project(a), project(b), project(c));
+
+    let u = cx * by - cy * bx;
+    let v = ax * cy - ay * cx;
+    let w = bx * ay - by * ax;
+    if (u < 0. || v < 0. || w < 0.) && (u > 0. || v > 0. || w > 0.) {
+        return None;
+    }
+
+    let determinant = u + v + w;
+    if determinant == 0. {
+        return None;
+    }
+
+    let t = (u * a[kz] + v * b[kz] + w * c[kz]) * shear_z / determinant;
+    if t_min < t && t < t_max {
+        Some((t, [u / determinant, v / determinant, w / determinant]))
+    } else {
+        None
+    }
+}
+
+pub fn barycentric(position: &Point, vertices: &[Point; 3]) -> [f64; 3] {
+    let [a, b, c] = *vertices;
+    let (ab, ac, ap) = (b - a, c - a, *position - a);
+
+    let (d00, d01, d11) = (ab.dot(&ab), ab.dot(&ac), ac.dot(&ac));
+    let (d20, d21) = (ap.dot(&ab), ap.dot(&ac));
+    let denominator = d00 * d11 - d01 * d01;
+
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+    [1. - v - w, v, w]
+}
+
+pub fn interpolate(
+    position: Point,
+    barycentric: [f64; 3],
+    vertices: &[Point; 3],
+    normals: Option<&[Point; 3]>,
+    uvs: Option<&[(f64, f64); 3]>,
+) -> ShapeHit {
+    let [b0, b1, b2] = barycentric;
+    let normal = match normals {
+        Some([n0, n1, n2]) => (b0 * *n0 + b1 * *n1 + b2 * *n2).normalized(),
+        None => geometric_normal(vertices),
+    };
+    let (u, v) = match uvs {
+        Some([uv0, uv1, uv2]) => (
+            b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0,
+            b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1,
+        ),
+        None => (b1, b2),
+    };
+    let (dpdu, dpdv) = derivatives(vertices, uvs);
+    let (tangent, bitangent) = tangent_frame(&normal, &dpdu, &dpdv);
+
+    ShapeHit {
+        position,
+        normal,
+        tangent,
+        bitangent,
+        u,
+        v,
+        dpdu,
+        dpdv,
+    }
+}
+
+fn derivatives(vertices: &[Point; 3], uvs: Option<&[(f64, f64); 3]>) -> (Point, Point) {
+    let [a, b, c] = *vertices;
+    let (e1, e2) = (b - a, c - a);
+    let Some([uv0, uv1, uv2]) = uvs else {
+        return (e1, e2);
+    };
+
+    let (du1, dv1) = (uv1.0 - uv0.0, uv1.1 - uv0.1);
+    let (du2, dv2) = (uv2.0 - uv0.0, uv2.1 - uv0.1);
+    let determinant = du1 * dv2 - dv1 * du2;
+    if determinant.abs() < TOLERANCE {
+        return (e1, e2);
+    }
+    (
+        (dv2 * e1 - dv1 * e2) / determinant,
+        (du1 * e2 - du2 * e1) / determinant,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        let vertices = [
+            Point::from_xyz(1., 0., 0.),
+            Point::from_xyz(1., 1., 0.),
+            Point::from_xyz(1., 0., 1.),
+        ];
+        let ray = Ray::new(Point::default(), Point::from_xyz(2., 0.5, 0.25));
+
+        let (t, barycentric) = intersect(ray, &vertices, 0., f64::INFINITY).unwrap();
+        assert_eq!(t, 0.5);
+        assert_eq!(
+            Point { coord: barycentric },
+            Point::from_xyz(0.625, 0.25, 0.125)
+        );
+        assert_eq!(intersect(ray, &vertices, 0., 0.5), None);
+    }
+
+    #[test]
+    fn test_intersect_shared_edge() {
+        let quad = [
+            Point::from_xyz(0., 0., 1.),
+            Point::from_xyz(1., 0., 1.),
+            Point::from_xyz(1., 1., 1.),
+            Point::from_xyz(0., 1., 1.),
+        ];
+        let first = [quad[0], quad[1], quad[2]];
+        let second = [quad[0], quad[2], quad[3]];
+
+        for i in 0..100 {
+            let x = i as f64 / 99.;
+            let ray = Ray::new(
+                Point::from_xyz(0.3, 0.7, 0.),
+                Point::from_xyz(x - 0.3, x - 0.7, 1.),
+            );
+            assert!(
+                intersect(ray, &first, 0., f64::INFINITY).is_some()
+                    || intersect(ray, &second, 0., f64::INFINITY).is_some()
+            );
+        }
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let vertices = [
+            Point::from_xyz(0., 0., 0.),
+            Point::from_xyz(1., 0., 0.),
+            Point::from_xyz(0., 1., 0.),
+        ];
+
+        assert_eq!(
+            Point {
+                coord: barycentric(&Point::from_xyz(0.25, 0.5, 0.), &vertices)
+            },
+            Point::from_xyz(0.25, 0.25, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let vertices = [
+            Point::from_xyz(0., 0., 0.),
+            Point::from_xyz(2., 0., 0.),
+            Point::from_xyz(0., 4., 0.),
+        ];
+        let uvs = [(0., 1.), (1., 1.), (0., 0.)];
+
+        assert_eq!(
+            derivatives(&vertices, Some(&uvs)),
+            (Point::from_xyz(2., 0., 0.), Point::from_xyz(0., -4., 0.))
+        );
+    }
+}
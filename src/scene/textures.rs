@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use image::error::{ImageError, UnsupportedErrorKind};
+use image::io::Reader;
+use image::Rgba32FImage;
+
+use super::error::SceneError;
+use super::object::colors::mipmap::MipMap;
+use super::object::colors::texture::{linearize, ColorSpace};
+
+#[derive(Debug, Default)]
+pub struct TextureRegistry {
+    images: HashMap<(PathBuf, ColorSpace), Arc<MipMap>>,
+}
+
+impl TextureRegistry {
+    pub fn get(&mut self, path: &Path, color_space: ColorSpace) -> Result<Arc<MipMap>, SceneError> {
+        self.get_or_insert_with(path, color_space, || decode(path, color_space))
+    }
+
+    pub fn get_or_insert_with<E>(
+        &mut self,
+        path: &Path,
+        color_space: ColorSpace,
+        image: impl FnOnce() -> Result<Rgba32FImage, E>,
+    ) -> Result<Arc<MipMap>, E> {
+        let key = (path.to_owned(), color_space);
+        if let Some(image) = self.images.get(&key) {
+            return Ok(image.clone());
+        }
+        let image = Arc::new(MipMap::new(image()?));
+        self.images.insert(key, image.clone());
+        Ok(image)
+    }
+
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.images.keys().map(|(path, _)| path.as_path())
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.images.values().map(|image| image.memory_usage()).sum()
+    }
+}
+
+fn decode(path: &Path, color_space: ColorSpace) -> Result<Rgba32FImage, SceneError> {
+    let reader = Reader::open(path).map_err(|source| match source.kind() {
+        ErrorKind::NotFound => SceneError::MissingTexture {
+            path: path.to_owned(),
+        },
+        _ => SceneError::Io {
+            path: path.to_owned(),
+            source,
+        },
+    })?;
+    let image = reader.decode().map_err(|error| match error {
+        ImageError::Unsupported(error) => match error.kind() {
+            UnsupportedErrorKind::Color(format) => SceneError::UnsupportedPixelFormat {
+                path: path.to_owned(),
+                format: format!("{format:?}"),
+            },
+            _ => SceneError::InvalidTexture {
+                path: path.to_owned(),
+                message: error.to_string(),
+            },
+        },
+        ImageError::IoError(source) => SceneError::Io {
+            path: path.to_owned(),
+            source,
+        },
+        error => SceneError::InvalidTexture {
+            path: path.to_owned(),
+            message: error.to_string(),
+        },
+    })?;
+    Ok(linearize(image, color_space))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shared_images() {
+        let path = std::env::temp_dir().join("raytracer_rust_test_texture.png");
+        image::RgbaImage::new(4, 2).save(&path).unwrap();
+
+        let mut textures = TextureRegistry::default();
+        let first = textures.get(&path, ColorSpace::Srgb).unwrap();
+        let second = textures.get(&path, ColorSpace::Srgb).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(textures.paths().collect::<Vec<_>>(), [path.as_path()]);
+        assert_eq!(textures.memory_usage(), (8 + 2 + 1) * 4 * 4);
+    }
+
+    #[test]
+    fn test_missing_texture() {
+        let error = TextureRegistry::default()
+            .get(Path::new("data/missing.png"), ColorSpace::Srgb)
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            SceneError::MissingTexture { path } if path == Path::new("data/missing.png")
+        ));
+    }
+}
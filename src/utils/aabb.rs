@@ -0,0 +1,170 @@
+use super::point::{Point, DIMENSIONS};
+use super::ray::Ray;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Point,
+    pub max: Point,
+}
+
+pub struct InverseRay {
+    pub origin: Point,
+    pub inverse_direction: Point,
+}
+
+impl From<Ray> for InverseRay {
+    fn from(ray: Ray) -> InverseRay {
+        let mut inverse_direction = ray.direction;
+        for x in inverse_direction.coord.iter_mut() {
+            *x = 1. / *x;
+        }
+
+        InverseRay {
+            origin: ray.origin,
+            inverse_direction,
+        }
+    }
+}
+
+impl Aabb {
+    pub fn new(a: Point, b: Point) -> Aabb {
+        Aabb::empty().grow(&a).grow(&b)
+    }
+
+    pub fn empty() -> Aabb {
+        Aabb {
+            min: Point {
+                coord: [f64::INFINITY; DIMENSIONS],
+            },
+            max: Point {
+                coord: [f64::NEG_INFINITY; DIMENSIONS],
+            },
+        }
+    }
+
+    pub fn unbounded() -> Aabb {
+        Aabb {
+            min: Point {
+                coord: [f64::NEG_INFINITY; DIMENSIONS],
+            },
+            max: Point {
+                coord: [f64::INFINITY; DIMENSIONS],
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..DIMENSIONS).any(|axis| self.min.coord[axis] > self.max.coord[axis])
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.min.coord.iter().all(|x| x.is_finite()) && self.max.coord.iter().all(|x| x.is_finite())
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        let mut union = *self;
+        for axis in 0..DIMENSIONS {
+            union.min.coord[axis] = self.min.coord[axis].min(other.min.coord[axis]);
+            union.max.coord[axis] = self.max.coord[axis].max(other.max.coord[axis]);
+        }
+        union
+    }
+
+    pub fn grow(&self, point: &Point) -> Aabb {
+        self.union(&Aabb {
+            min: *point,
+            max: *point,
+        })
+    }
+
+    pub fn size(&self) -> Point {
+        self.max - self.min
+    }
+
+    pub fn centroid(&self) -> Point {
+        (self.min + self.max) / 2.
+    }
+
+    pub fn corners(&self) -> [Point; 8] {
+        let mut corners = [self.min; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            for axis in 0..DIMENSIONS {
+                if i & (1 << axis) != 0 {
+                    corner.coord[axis] = self.max.coord[axis];
+                }
+            }
+        }
+        corners
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let size = self.size();
+        2. * (size.x() * size.y() + size.y() * size.z() + size.z() * size.x())
+    }
+
+    pub fn hit(&self, ray: &InverseRay, t_min: f64, t_max: f64) -> Option<f64> {
+        let (mut t_min, mut t_max) = (t_min, t_max);
+        for axis in 0..DIMENSIONS {
+            let t0 =
+                (self.min.coord[axis] - ray.origin.coord[axis]) * ray.inverse_direction.coord[axis];
+            let t1 =
+                (self.max.coord[axis] - ray.origin.coord[axis]) * ray.inverse_direction.coord[axis];
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+        if t_min <= t_max {
+            Some(t_min)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unit_box(center: Point) -> Aabb {
+        let half = Point::from_xyz(0.5, 0.5, 0.5);
+        Aabb::new(center - half, center + half)
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            Aabb::new(Point::from_xyz(1., -1., 2.), Point::from_xyz(-1., 1., 0.)),
+            Aabb {
+                min: Point::from_xyz(-1., -1., 0.),
+                max: Point::from_xyz(1., 1., 2.),
+            }
+        );
+    }
+
+    #[test]
+    fn test_bounded() {
+        assert!(unit_box(Point::default()).is_bounded());
+        assert!(!Aabb::unbounded().is_bounded());
+        assert!(Aabb::empty().is_empty());
+    }
+
+    #[test]
+    fn test_surface_area() {
+        assert_eq!(unit_box(Point::default()).surface_area(), 6.);
+        assert_eq!(Aabb::empty().surface_area(), 0.);
+    }
+
+    #[test]
+    fn test_hit() {
+        let aabb = unit_box(Point::from_xyz(3., 0., 0.));
+        let ray = Ray::new(Point::default(), Point::from_xyz(1., 0., 0.));
+        let backward_ray = Ray::new(ray.origin, -ray.direction);
+
+        assert_eq!(aabb.hit(&ray.into(), 0., f64::INFINITY), Some(2.5));
+        assert_eq!(aabb.hit(&ray.into(), 0., 2.), None);
+        assert_eq!(aabb.hit(&backward_ray.into(), 0., f64::INFINITY), None);
+        assert_eq!(Aabb::unbounded().hit(&ray.into(), 0., 1.), Some(0.));
+    }
+}
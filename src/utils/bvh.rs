@@ -0,0 +1,245 @@
+use super::aabb::{Aabb, InverseRay};
+use super::point::{Point, DIMENSIONS};
+use super::ray::Ray;
+
+const BINS: usize = 12;
+const TRAVERSAL_COST: f64 = 1.;
+const INTERSECTION_COST: f64 = 1.;
+const MAX_LEAF_SIZE: usize = 8;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf { first: usize, count: usize },
+    Interior { second_child: usize },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Bvh {
+    bounds: Vec<Aabb>,
+    nodes: Vec<Node>,
+    indices: Vec<usize>,
+}
+
+struct Bin {
+    bounds: Aabb,
+    count: usize,
+}
+
+impl Bvh {
+    pub fn new(boxes: &[Aabb]) -> Bvh {
+        let mut bvh = Bvh {
+            bounds: Vec::with_capacity(2 * boxes.len()),
+            nodes: Vec::with_capacity(2 * boxes.len()),
+            indices: (0..boxes.len()).collect(),
+        };
+        if !boxes.is_empty() {
+            let centroids: Vec<Point> = boxes.iter().map(Aabb::centroid).collect();
+            bvh.build(boxes, &centroids, 0, boxes.len());
+        }
+        bvh
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    fn build(&mut self, boxes: &[Aabb], centroids: &[Point], first: usize, count: usize) {
+        let items = &self.indices[first..first + count];
+        let bounds = items
+            .iter()
+            .fold(Aabb::empty(), |bounds, &i| bounds.union(&boxes[i]));
+        let centroid_bounds = items
+            .iter()
+            .fold(Aabb::empty(), |bounds, &i| bounds.grow(&centroids[i]));
+
+        let node = self.nodes.len();
+        self.bounds.push(bounds);
+        self.nodes.push(Node::Leaf { first, count });
+        if count == 1 {
+            return;
+        }
+
+        let leaf_cost = INTERSECTION_COST * count as f64;
+        let Some((axis, split, cost)) =
+            Self::find_split(boxes, centroids, items, &bounds, &centroid_bounds)
+        else {
+            return;
+        };
+        if cost >= leaf_cost && count <= MAX_LEAF_SIZE {
+            return;
+        }
+
+        let (min, extent) = (
+            centroid_bounds.min.coord[axis],
+            centroid_bounds.max.coord[axis] - centroid_bounds.min.coord[axis],
+        );
+        let items = &mut self.indices[first..first + count];
+        let mut middle = 0;
+        for i in 0..count {
+            if Self::bin_index(centroids[items[i]].coord[axis], min, extent) < split {
+                items.swap(i, middle);
+                middle += 1;
+            }
+        }
+        if middle == 0 || middle == count {
+            middle = count / 2;
+        }
+
+        self.build(boxes, centroids, first, middle);
+        let second_child = self.nodes.len();
+        self.build(boxes, centroids, first + middle, count - middle);
+        self.nodes[node] = Node::Interior { second_child };
+    }
+
+    fn bin_index(centroid: f64, min: f64, extent: f64) -> usize {
+        (((centroid - min) / extent * BINS as f64) as usize).min(BINS - 1)
+    }
+
+    fn find_split(
+        boxes: &[Aabb],
+        centroids: &[Point],
+        items: &[usize],
+        bounds: &Aabb,
+        centroid_bounds: &Aabb,
+    ) -> Option<(usize, usize, f64)> {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for axis in 0..DIMENSIONS {
+            let (min, extent) = (
+                centroid_bounds.min.coord[axis],
+                centroid_bounds.max.coord[axis] - centroid_bounds.min.coord[axis],
+            );
+            if extent <= 0. {
+                continue;
+            }
+
+            let mut bins: Vec<Bin> = (0..BINS)
+                .map(|_| Bin {
+                    bounds: Aabb::empty(),
+                    count: 0,
+                })
+                .collect();
+            for &i in items {
+                let bin = &mut bins[Self::bin_index(centroids[i].coord[axis], min, extent)];
+                bin.bounds = bin.bounds.union(&boxes[i]);
+                bin.count += 1;
+            }
+
+            let mut right_areas = [0.; BINS];
+            let mut right_counts = [0; BINS];
+            let (mut right_bounds, mut right_count) = (Aabb::empty(), 0);
+            for split in (1..BINS).rev() {
+                right_bounds = right_bounds.union(&bins[split].bounds);
+                right_count += bins[split].count;
+                right_areas[split] = right_bounds.surface_area();
+                right_counts[split] = right_count;
+            }
+
+            let (mut left_bounds, mut left_count) = (Aabb::empty(), 0);
+            for split in 1..BINS {
+                left_bounds = left_bounds.union(&bins[split - 1].bounds);
+                left_count += bins[split - 1].count;
+                if left_count == 0 || right_counts[split] == 0 {
+                    continue;
+                }
+                let cost = TRAVERSAL_COST
+                    + INTERSECTION_COST
+                        * (left_bounds.surface_area() * left_count as f64
+                            + right_areas[split] * right_counts[split] as f64)
+                        / bounds.surface_area();
+                if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                    best = Some((axis, split, cost));
+                }
+            }
+        }
+        best
+    }
+
+    pub fn hit(
+        &self,
+        ray: Ray,
+        t_min: f64,
+        t_max: f64,
+        mut hit_item: impl FnMut(usize, f64, f64) -> Option<f64>,
+    ) -> Option<f64> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let inverse_ray = InverseRay::from(ray);
+        let mut t_max = t_max;
+        let mut closest_t = None;
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            if self.bounds[node].hit(&inverse_ray, t_min, t_max).is_none() {
+                continue;
+            }
+            match self.nodes[node] {
+                Node::Leaf { first, count } => {
+                    for &item in &self.indices[first..first + count] {
+                        if let Some(t) = hit_item(item, t_min, t_max) {
+                            t_max = t;
+                            closest_t = Some(t);
+                        }
+                    }
+                }
+                Node::Interior { second_child } => {
+                    let first_child = node + 1;
+                    let t_first = self.bounds[first_child].hit(&inverse_ray, t_min, t_max);
+                    let t_second = self.bounds[second_child].hit(&inverse_ray, t_min, t_max);
+                    match (t_first, t_second) {
+                        (Some(t_first), Some(t_second)) if t_second < t_first => {
+                            stack.push(first_child);
+                            stack.push(second_child);
+                        }
+                        (Some(_), Some(_)) => {
+                            stack.push(second_child);
+                            stack.push(first_child);
+                        }
+                        (Some(_), None) => stack.push(first_child),
+                        (None, Some(_)) => stack.push(second_child),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        closest_t
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unit_box(center: Point) -> Aabb {
+        let half = Point::from_xyz(0.5, 0.5, 0.5);
+        Aabb {
+            min: center - half,
+            max: center + half,
+        }
+    }
+
+    #[test]
+    fn test_bvh_hit_closest() {
+        let boxes: Vec<Aabb> = (0..100)
+            .map(|i| unit_box(Point::from_xyz(i as f64 * 2., (i % 7) as f64, 0.)))
+            .collect();
+        let bvh = Bvh::new(&boxes);
+        let ray = Ray::new(Point::from_xyz(-10., 0., 0.), Point::from_xyz(1., 0., 0.));
+
+        let inverse_ray = InverseRay::from(ray);
+        let mut closest = None;
+        let t = bvh.hit(ray, 0., f64::INFINITY, |item, t_min, t_max| {
+            let t = boxes[item].hit(&inverse_ray, t_min, t_max)?;
+            closest = Some(item);
+            Some(t)
+        });
+
+        assert_eq!(bvh.len(), 100);
+        assert_eq!(t, Some(9.5));
+        assert_eq!(closest, Some(0));
+    }
+}
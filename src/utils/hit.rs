@@ -1,5 +1,6 @@
 use palette::LinSrgb;
 
+use crate::scene::object::shapes::TOLERANCE;
 use crate::scene::object::Object;
 use crate::utils::point::Point;
 use crate::utils::ray::Ray;
@@ -8,14 +9,69 @@ pub struct Hit<'object> {
     pub ray: Ray,
     pub object: &'object Object,
     pub t: f64,
+    pub primitive: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    pub t: f64,
+    pub primitive: usize,
+}
+
+impl Intersection {
+    pub fn new(t: f64) -> Intersection {
+        Intersection { t, primitive: 0 }
+    }
+}
+
+#[derive(Clone, Copy)]
 pub struct ShapeHit {
     pub position: Point,
     pub normal: Point,
+    pub tangent: Point,
+    pub bitangent: Point,
 
     pub u: f64,
     pub v: f64,
+    pub dpdu: Point,
+    pub dpdv: Point,
+}
+
+#[cfg(test)]
+impl ShapeHit {
+    pub fn flat(position: Point, u: f64, v: f64) -> ShapeHit {
+        ShapeHit {
+            position,
+            normal: Point::from_xyz(0., 0., 1.),
+            tangent: Point::from_xyz(1., 0., 0.),
+            bitangent: Point::from_xyz(0., 1., 0.),
+            u,
+            v,
+            dpdu: Point::from_xyz(1., 0., 0.),
+            dpdv: Point::from_xyz(0., 1., 0.),
+        }
+    }
+}
+
+pub fn tangent_frame(normal: &Point, dpdu: &Point, dpdv: &Point) -> (Point, Point) {
+    let tangent = [
+        *dpdu,
+        dpdv.cross(normal),
+        Point::from_xyz(1., 0., 0.),
+        Point::from_xyz(0., 1., 0.),
+    ]
+    .into_iter()
+    .map(|direction| direction - normal.dot(&direction) * *normal)
+    .find(|tangent| tangent.norm() > TOLERANCE)
+    .unwrap_or_default()
+    .normalized();
+
+    let bitangent = normal.cross(&tangent);
+    if bitangent.dot(dpdv) < 0. {
+        (tangent, -bitangent)
+    } else {
+        (tangent, bitangent)
+    }
 }
 
 pub struct HitInfo<'object> {
@@ -27,7 +83,7 @@ pub struct HitInfo<'object> {
 }
 
 pub trait Hittable {
-    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Hit>;
+    fn hit(&self, ray: Ray, t_min: f64, t_max: f64) -> Option<Hit<'_>>;
 }
 
 impl<'object> From<Hit<'object>> for HitInfo<'object> {
@@ -35,3 +91,23 @@ impl<'object> From<Hit<'object>> for HitInfo<'object> {
         hit.object.get_hit_info(hit)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tangent_frame() {
+        let normal = Point::from_xyz(0., 0., 1.);
+        let (tangent, bitangent) = tangent_frame(
+            &normal,
+            &Point::from_xyz(2., 0., 1.),
+            &Point::from_xyz(0., -3., 0.),
+        );
+        assert_eq!(tangent, Point::from_xyz(1., 0., 0.));
+        assert_eq!(bitangent, Point::from_xyz(0., -1., 0.));
+
+        let (tangent, _) = tangent_frame(&normal, &normal, &Point::from_xyz(0., 1., 0.));
+        assert_eq!(tangent, Point::from_xyz(1., 0., 0.));
+    }
+}
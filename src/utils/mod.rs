@@ -1,4 +1,9 @@
+pub mod aabb;
+pub mod bvh;
 pub mod hit;
+pub mod noise;
 pub mod point;
 pub mod random;
 pub mod ray;
+pub mod spectrum;
+pub mod transform;
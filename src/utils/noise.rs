@@ -0,0 +1,121 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use super::point::Point;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "u64", into = "u64")]
+pub struct Perlin {
+    seed: u64,
+    permutation: Vec<u8>,
+}
+
+impl From<u64> for Perlin {
+    fn from(seed: u64) -> Perlin {
+        let mut permutation: Vec<u8> = (0..=255).collect();
+        permutation.shuffle(&mut StdRng::seed_from_u64(seed));
+        permutation.extend_from_within(..);
+        Perlin { seed, permutation }
+    }
+}
+
+impl From<Perlin> for u64 {
+    fn from(perlin: Perlin) -> u64 {
+        perlin.seed
+    }
+}
+
+impl Perlin {
+    pub fn noise(&self, point: &Point) -> f64 {
+        let cell = point.coord.map(|x| x.floor());
+        let [x, y, z] = [0, 1, 2].map(|axis| point.coord[axis] - cell[axis]);
+        let [i, j, k] = cell.map(|x| (x as i64).rem_euclid(256) as usize);
+        let [u, v, w] = [x, y, z].map(fade);
+
+        let p = &self.permutation;
+        let hash =
+            |di: usize, dj: usize, dk: usize| p[p[p[i + di] as usize + j + dj] as usize + k + dk];
+        let corner = |di: usize, dj: usize, dk: usize| {
+            gradient(
+                hash(di, dj, dk),
+                x - di as f64,
+                y - dj as f64,
+                z - dk as f64,
+            )
+        };
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+                lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+            ),
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+                lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+            ),
+        )
+    }
+
+    pub fn fbm(&self, point: &Point, octaves: u32) -> f64 {
+        self.octaves(point, octaves, |noise| noise)
+    }
+
+    pub fn turbulence(&self, point: &Point, octaves: u32) -> f64 {
+        self.octaves(point, octaves, f64::abs)
+    }
+
+    fn octaves(&self, point: &Point, octaves: u32, shape: impl Fn(f64) -> f64) -> f64 {
+        let (mut sum, mut total, mut amplitude, mut frequency) = (0., 0., 1., 1.);
+        for _ in 0..octaves.max(1) {
+            sum += amplitude * shape(self.noise(&(frequency * *point)));
+            total += amplitude;
+            amplitude /= 2.;
+            frequency *= 2.;
+        }
+        sum / total
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn gradient(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_noise() {
+        let perlin = Perlin::from(7);
+        let point = Point::from_xyz(1.3, -2.7, 0.4);
+
+        assert_eq!(perlin.noise(&Point::from_xyz(1., 2., 3.)), 0.);
+        assert_eq!(perlin.noise(&point), Perlin::from(7).noise(&point));
+        assert_ne!(perlin.noise(&point), Perlin::from(8).noise(&point));
+        for i in 0..100 {
+            let point = Point::from_xyz(i as f64 * 0.37, i as f64 * -0.19, i as f64 * 0.11);
+            assert!(perlin.fbm(&point, 4).abs() <= 1.);
+            assert!((0. ..=1.).contains(&perlin.turbulence(&point, 4)));
+        }
+    }
+}
@@ -64,7 +64,7 @@ impl ops::Add<Point> for Point {
     type Output = Point;
 
     fn add(self, other: Point) -> Point {
-        let mut coord = self.coord.clone();
+        let mut coord = self.coord;
         for (i, x) in other.coord.iter().enumerate() {
             coord[i] += x;
         }
@@ -77,7 +77,7 @@ impl ops::Sub<Point> for Point {
     type Output = Point;
 
     fn sub(self, other: Point) -> Point {
-        let mut coord = self.coord.clone();
+        let mut coord = self.coord;
         for (i, x) in other.coord.iter().enumerate() {
             coord[i] -= x;
         }
@@ -90,7 +90,7 @@ impl ops::Mul<f64> for Point {
     type Output = Point;
 
     fn mul(self, scale: f64) -> Point {
-        let mut coord = self.coord.clone();
+        let mut coord = self.coord;
         for x in coord.iter_mut() {
             *x *= scale
         }
@@ -103,7 +103,7 @@ impl ops::Mul<Point> for f64 {
     type Output = Point;
 
     fn mul(self, point: Point) -> Point {
-        let mut coord = point.coord.clone();
+        let mut coord = point.coord;
         for x in coord.iter_mut() {
             *x *= self
         }
@@ -116,7 +116,7 @@ impl ops::Div<f64> for Point {
     type Output = Point;
 
     fn div(self, scale: f64) -> Point {
-        let mut coord = self.coord.clone();
+        let mut coord = self.coord;
         for x in coord.iter_mut() {
             *x /= scale
         }
@@ -137,10 +137,6 @@ impl PartialEq for Point {
     fn eq(&self, other: &Self) -> bool {
         zip(self.coord, other.coord).all(|(x1, x2)| relative_eq!(x1, x2))
     }
-
-    fn ne(&self, other: &Self) -> bool {
-        !Point::eq(&self, &other)
-    }
 }
 
 #[cfg(test)]
@@ -1,13 +1,75 @@
 use super::point::Point;
 
+const ROUGH_SPREAD: f64 = 0.5;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Ray {
     pub origin: Point,
     pub direction: Point,
+
+    pub width: f64,
+    pub spread: f64,
+    pub wavelength: Option<f64>,
 }
 
 impl Ray {
+    pub fn new(origin: Point, direction: Point) -> Ray {
+        Ray {
+            origin,
+            direction,
+            width: 0.,
+            spread: 0.,
+            wavelength: None,
+        }
+    }
+
     pub fn at_t(&self, t:f64) -> Point {
         self.origin + t * self.direction
     }
-}
\ No newline at end of file
+
+    pub fn width_at(&self, distance: f64) -> f64 {
+        self.width + self.spread * distance
+    }
+
+    pub fn scattered(&self, origin: Point, direction: Point) -> Ray {
+        Ray {
+            origin,
+            direction,
+            width: self.width_at((origin - self.origin).norm()),
+            spread: self.spread,
+            wavelength: self.wavelength,
+        }
+    }
+
+    pub fn scattered_rough(&self, origin: Point, direction: Point, roughness: f64) -> Ray {
+        Ray {
+            spread: self.spread.max(roughness * ROUGH_SPREAD),
+            ..self.scattered(origin, direction)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scattered() {
+        let ray = Ray {
+            width: 0.5,
+            spread: 0.25,
+            ..Ray::new(Point::default(), Point::from_xyz(0., 0., 1.))
+        };
+        let origin = Point::from_xyz(0., 0., 2.);
+        let direction = Point::from_xyz(1., 0., 0.);
+
+        let mirrored = ray.scattered(origin, direction);
+        assert_eq!(mirrored.width, 1.);
+        assert_eq!(mirrored.spread, 0.25);
+
+        let diffused = ray.scattered_rough(origin, direction, 1.);
+        assert_eq!(diffused.width, 1.);
+        assert_eq!(diffused.spread, ROUGH_SPREAD);
+        assert_eq!(ray.scattered_rough(origin, direction, 0.).spread, 0.25);
+    }
+}
@@ -0,0 +1,241 @@
+use std::sync::OnceLock;
+
+use palette::LinSrgb;
+use rand::random;
+use serde::{Deserialize, Serialize};
+
+pub const MIN_WAVELENGTH: f64 = 380.;
+pub const MAX_WAVELENGTH: f64 = 780.;
+pub const MAX_LUMINOUS_EFFICACY: f64 = 683.;
+
+const PLANCK: f64 = 6.62607015e-34;
+const BOLTZMANN: f64 = 1.380649e-23;
+const LIGHT_SPEED: f64 = 299792458.;
+
+const SMITS_WAVELENGTH: f64 = 720.;
+const SMITS_WHITE: [f64; 10] = [
+    1.0000, 1.0000, 0.9999, 0.9993, 0.9992, 0.9998, 1.0000, 1.0000, 1.0000, 1.0000,
+];
+const SMITS_CYAN: [f64; 10] = [
+    0.9710, 0.9426, 1.0007, 1.0007, 1.0007, 1.0007, 0.1564, 0.0000, 0.0000, 0.0000,
+];
+const SMITS_MAGENTA: [f64; 10] = [
+    1.0000, 1.0000, 0.9685, 0.2229, 0.0000, 0.0458, 0.8369, 1.0000, 1.0000, 0.9959,
+];
+const SMITS_YELLOW: [f64; 10] = [
+    0.0001, 0.0000, 0.1088, 0.6651, 1.0000, 1.0000, 0.9996, 0.9586, 0.9685, 0.9840,
+];
+const SMITS_RED: [f64; 10] = [
+    0.1012, 0.0515, 0.0000, 0.0000, 0.0000, 0.0000, 0.8325, 1.0149, 1.0149, 1.0149,
+];
+const SMITS_GREEN: [f64; 10] = [
+    0.0000, 0.0000, 0.0273, 0.7937, 1.0000, 0.9418, 0.1719, 0.0000, 0.0000, 0.0025,
+];
+const SMITS_BLUE: [f64; 10] = [
+    1.0000, 1.0000, 0.8916, 0.3323, 0.0000, 0.0000, 0.0003, 0.0369, 0.0483, 0.0496,
+];
+
+pub fn sample_wavelength() -> f64 {
+    MIN_WAVELENGTH + random::<f64>() * (MAX_WAVELENGTH - MIN_WAVELENGTH)
+}
+
+fn lobe(wavelength: f64, mean: f64, low: f64, high: f64) -> f64 {
+    let t = (wavelength - mean) / if wavelength < mean { low } else { high };
+    (-0.5 * t * t).exp()
+}
+
+pub fn cie_xyz(wavelength: f64) -> [f64; 3] {
+    [
+        1.056 * lobe(wavelength, 599.8, 37.9, 31.0) + 0.362 * lobe(wavelength, 442.0, 16.0, 26.7)
+            - 0.065 * lobe(wavelength, 501.1, 20.4, 26.2),
+        0.821 * lobe(wavelength, 568.8, 46.9, 40.5) + 0.286 * lobe(wavelength, 530.9, 16.3, 31.1),
+        1.217 * lobe(wavelength, 437.0, 11.8, 36.0) + 0.681 * lobe(wavelength, 459.0, 26.0, 13.8),
+    ]
+}
+
+pub fn xyz_to_rgb([x, y, z]: [f64; 3]) -> [f64; 3] {
+    [
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.9692660 * x + 1.8760108 * y + 0.0415560 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    ]
+}
+
+pub fn integrate<F: Fn(f64) -> [f64; 3]>(function: F) -> [f64; 3] {
+    let steps = (MAX_WAVELENGTH - MIN_WAVELENGTH) as usize;
+    let mut total = [0.; 3];
+    for step in 0..steps {
+        let value = function(MIN_WAVELENGTH + step as f64 + 0.5);
+        for (total, value) in total.iter_mut().zip(value) {
+            *total += value;
+        }
+    }
+    total
+}
+
+fn white_balance() -> [f64; 3] {
+    static WHITE: OnceLock<[f64; 3]> = OnceLock::new();
+    *WHITE.get_or_init(|| {
+        let range = MAX_WAVELENGTH - MIN_WAVELENGTH;
+        integrate(|wavelength| xyz_to_rgb(cie_xyz(wavelength))).map(|total| range / total)
+    })
+}
+
+pub fn wavelength_to_rgb(wavelength: f64) -> LinSrgb {
+    let rgb = xyz_to_rgb(cie_xyz(wavelength));
+    let [red, green, blue] = [0, 1, 2].map(|c| (rgb[c] * white_balance()[c]) as f32);
+    LinSrgb::new(red, green, blue)
+}
+
+pub fn upsample(color: LinSrgb, wavelength: f64) -> f64 {
+    let bins = SMITS_WHITE.len();
+    let bin = ((wavelength - MIN_WAVELENGTH) / (SMITS_WAVELENGTH - MIN_WAVELENGTH) * bins as f64)
+        .clamp(0., (bins - 1) as f64) as usize;
+    let [red, green, blue] = [color.red, color.green, color.blue].map(f64::from);
+
+    let (white, first, second) = if red <= green && red <= blue {
+        if green <= blue {
+            (red, (green - red, SMITS_CYAN), (blue - green, SMITS_BLUE))
+        } else {
+            (red, (blue - red, SMITS_CYAN), (green - blue, SMITS_GREEN))
+        }
+    } else if green <= red && green <= blue {
+        if red <= blue {
+            (
+                green,
+                (red - green, SMITS_MAGENTA),
+                (blue - red, SMITS_BLUE),
+            )
+        } else {
+            (
+                green,
+                (blue - green, SMITS_MAGENTA),
+                (red - blue, SMITS_RED),
+            )
+        }
+    } else if red <= green {
+        (blue, (red - blue, SMITS_YELLOW), (green - red, SMITS_GREEN))
+    } else {
+        (blue, (green - blue, SMITS_YELLOW), (red - green, SMITS_RED))
+    };
+    white * SMITS_WHITE[bin] + first.0 * first.1[bin] + second.0 * second.1[bin]
+}
+
+pub fn planck(temperature: f64, wavelength: f64) -> f64 {
+    let wavelength = wavelength * 1e-9;
+    2. * PLANCK * LIGHT_SPEED * LIGHT_SPEED
+        / (wavelength.powi(5)
+            * ((PLANCK * LIGHT_SPEED / (wavelength * BOLTZMANN * temperature)).exp() - 1.))
+}
+
+pub fn luminous_efficacy<F: Fn(f64) -> f64>(spectrum: F) -> f64 {
+    let [power, luminance, _] = integrate(|wavelength| {
+        let value = spectrum(wavelength);
+        [value, value * cie_xyz(wavelength)[1], 0.]
+    });
+    MAX_LUMINOUS_EFFICACY * luminance / power
+}
+
+pub fn equal_energy_efficacy() -> f64 {
+    static EFFICACY: OnceLock<f64> = OnceLock::new();
+    *EFFICACY.get_or_init(|| luminous_efficacy(|_| 1.))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "f64", into = "f64")]
+pub struct Blackbody {
+    temperature: f64,
+    scale: f64,
+    pub color: LinSrgb,
+    pub efficacy: f64,
+}
+
+impl From<f64> for Blackbody {
+    fn from(temperature: f64) -> Blackbody {
+        let range = MAX_WAVELENGTH - MIN_WAVELENGTH;
+        let [red, green, blue] = integrate(|wavelength| {
+            let color = wavelength_to_rgb(wavelength);
+            let value = planck(temperature, wavelength) / range;
+            [color.red, color.green, color.blue].map(|c| value * c as f64)
+        });
+        let scale = 1. / (0.2126 * red + 0.7152 * green + 0.0722 * blue);
+        Blackbody {
+            temperature,
+            scale,
+            color: LinSrgb::new(red as f32, green as f32, blue as f32) * scale as f32,
+            efficacy: luminous_efficacy(|wavelength| planck(temperature, wavelength)),
+        }
+    }
+}
+
+impl From<Blackbody> for f64 {
+    fn from(blackbody: Blackbody) -> f64 {
+        blackbody.temperature
+    }
+}
+
+impl Blackbody {
+    pub fn radiance(&self, wavelength: f64) -> f64 {
+        planck(self.temperature, wavelength) * self.scale
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_white_balance() {
+        let range = MAX_WAVELENGTH - MIN_WAVELENGTH;
+        let mean = integrate(|wavelength| {
+            let color = wavelength_to_rgb(wavelength);
+            [color.red, color.green, color.blue].map(|c| c as f64 / range)
+        });
+        for channel in mean {
+            assert!((channel - 1.).abs() < 1e-6);
+        }
+
+        let [_, luminance, _] = integrate(cie_xyz);
+        assert!((luminance - 106.9).abs() < 1.);
+        assert!(wavelength_to_rgb(450.).blue > wavelength_to_rgb(450.).red);
+        assert!(wavelength_to_rgb(650.).red > wavelength_to_rgb(650.).green);
+    }
+
+    #[test]
+    fn test_upsample() {
+        let range = MAX_WAVELENGTH - MIN_WAVELENGTH;
+        for color in [
+            LinSrgb::new(1., 1., 1.),
+            LinSrgb::new(0.8, 0.2, 0.1),
+            LinSrgb::new(0.1, 0.5, 0.3),
+            LinSrgb::new(0.2, 0.3, 0.9),
+        ] {
+            let rgb = integrate(|wavelength| {
+                let value = upsample(color, wavelength);
+                let color = wavelength_to_rgb(wavelength);
+                [color.red, color.green, color.blue].map(|c| value * c as f64 / range)
+            });
+            let expected = [color.red, color.green, color.blue];
+            for (channel, expected) in rgb.into_iter().zip(expected) {
+                assert!((channel - expected as f64).abs() < 0.1, "{rgb:?} {color:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_blackbody() {
+        let warm = Blackbody::from(3200.);
+        let cold = Blackbody::from(10000.);
+        assert!(warm.color.red > warm.color.blue);
+        assert!(cold.color.blue > cold.color.red);
+        assert!(warm.efficacy < cold.efficacy);
+        assert!((equal_energy_efficacy() - 182.).abs() < 2.);
+
+        let range = MAX_WAVELENGTH - MIN_WAVELENGTH;
+        let [red, _, _] = integrate(|wavelength| {
+            let value = warm.radiance(wavelength) * wavelength_to_rgb(wavelength).red as f64;
+            [value / range, 0., 0.]
+        });
+        assert!((red - warm.color.red as f64).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,323 @@
+use std::ops;
+
+use serde::{Deserialize, Serialize};
+
+use super::aabb::Aabb;
+use super::point::{Point, PointAsArray};
+use super::ray::Ray;
+
+pub type Matrix = [[f64; 4]; 4];
+
+const IDENTITY: Matrix = [
+    [1., 0., 0., 0.],
+    [0., 1., 0., 0.],
+    [0., 0., 1., 0.],
+    [0., 0., 0., 1.],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<TransformStep>", into = "Vec<TransformStep>")]
+pub struct Transform {
+    matrix: Matrix,
+    inverse: Matrix,
+}
+
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize)]
+pub enum TransformStep {
+    Translate(#[serde_as(as = "PointAsArray")] Point),
+    Scale(#[serde_as(as = "PointAsArray")] Point),
+    Rotate {
+        #[serde_as(as = "PointAsArray")]
+        axis: Point,
+        angle: f64,
+    },
+    Matrix(Matrix),
+}
+
+impl TryFrom<Vec<TransformStep>> for Transform {
+    type Error = String;
+
+    fn try_from(steps: Vec<TransformStep>) -> Result<Transform, String> {
+        let mut transform = Transform::identity();
+        for step in steps {
+            let step = match step {
+                TransformStep::Translate(offset) => Transform::translation(offset),
+                TransformStep::Scale(scale) => Transform::scaling(scale)
+                    .ok_or_else(|| "scale factors must not be zero".to_owned())?,
+                TransformStep::Rotate { axis, angle } => Transform::rotation(axis, angle),
+                TransformStep::Matrix(matrix) => Transform::from_matrix(matrix)
+                    .ok_or_else(|| "transform matrix is not invertible".to_owned())?,
+            };
+            transform = step * transform;
+        }
+        Ok(transform)
+    }
+}
+
+impl From<Transform> for Vec<TransformStep> {
+    fn from(transform: Transform) -> Vec<TransformStep> {
+        vec![TransformStep::Matrix(transform.matrix)]
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::identity()
+    }
+}
+
+impl Transform {
+    pub fn identity() -> Transform {
+        Transform {
+            matrix: IDENTITY,
+            inverse: IDENTITY,
+        }
+    }
+
+    pub fn translation(offset: Point) -> Transform {
+        let mut matrix = IDENTITY;
+        let mut inverse = IDENTITY;
+        for axis in 0..3 {
+            matrix[axis][3] = offset.coord[axis];
+            inverse[axis][3] = -offset.coord[axis];
+        }
+        Transform { matrix, inverse }
+    }
+
+    pub fn scaling(scale: Point) -> Option<Transform> {
+        if scale.coord.contains(&0.) {
+            return None;
+        }
+        let mut matrix = IDENTITY;
+        let mut inverse = IDENTITY;
+        for axis in 0..3 {
+            matrix[axis][axis] = scale.coord[axis];
+            inverse[axis][axis] = 1. / scale.coord[axis];
+        }
+        Some(Transform { matrix, inverse })
+    }
+
+    pub fn rotation(axis: Point, angle: f64) -> Transform {
+        let [x, y, z] = axis.normalized().coord;
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let c = 1. - cos;
+        let matrix = [
+            [
+                cos + x * x * c,
+                x * y * c - z * sin,
+                x * z * c + y * sin,
+                0.,
+            ],
+            [
+                y * x * c + z * sin,
+                cos + y * y * c,
+                y * z * c - x * sin,
+                0.,
+            ],
+            [
+                z * x * c - y * sin,
+                z * y * c + x * sin,
+                cos + z * z * c,
+                0.,
+            ],
+            [0., 0., 0., 1.],
+        ];
+        Transform {
+            matrix,
+            inverse: transpose(&matrix),
+        }
+    }
+
+    pub fn from_matrix(matrix: Matrix) -> Option<Transform> {
+        Some(Transform {
+            matrix,
+            inverse: invert(&matrix)?,
+        })
+    }
+
+    pub fn matrix(&self) -> &Matrix {
+        &self.matrix
+    }
+
+    pub fn inverse(&self) -> Transform {
+        Transform {
+            matrix: self.inverse,
+            inverse: self.matrix,
+        }
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn apply_point(&self, point: &Point) -> Point {
+        self.apply_vector(point)
+            + Point::from_xyz(self.matrix[0][3], self.matrix[1][3], self.matrix[2][3])
+    }
+
+    pub fn apply_vector(&self, vector: &Point) -> Point {
+        apply_linear(&self.matrix, vector)
+    }
+
+    pub fn apply_normal(&self, normal: &Point) -> Point {
+        apply_linear(&transpose(&self.inverse), normal).normalized()
+    }
+
+    pub fn apply_ray(&self, ray: &Ray) -> Ray {
+        Ray {
+            origin: self.apply_point(&ray.origin),
+            direction: self.apply_vector(&ray.direction),
+            ..*ray
+        }
+    }
+
+    pub fn apply_aabb(&self, aabb: &Aabb) -> Aabb {
+        if !aabb.is_bounded() {
+            return Aabb::unbounded();
+        }
+        aabb.corners().iter().fold(Aabb::empty(), |bounds, corner| {
+            bounds.grow(&self.apply_point(corner))
+        })
+    }
+}
+
+impl ops::Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, other: Transform) -> Transform {
+        Transform {
+            matrix: multiply(&self.matrix, &other.matrix),
+            inverse: multiply(&other.inverse, &self.inverse),
+        }
+    }
+}
+
+fn apply_linear(matrix: &Matrix, vector: &Point) -> Point {
+    let mut coord = [0.; 3];
+    for (row, x) in coord.iter_mut().enumerate() {
+        *x = (0..3).map(|k| matrix[row][k] * vector.coord[k]).sum();
+    }
+    Point { coord }
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut product = [[0.; 4]; 4];
+    for (row, product_row) in product.iter_mut().enumerate() {
+        for (column, x) in product_row.iter_mut().enumerate() {
+            *x = (0..4).map(|k| a[row][k] * b[k][column]).sum();
+        }
+    }
+    product
+}
+
+pub fn transpose(matrix: &Matrix) -> Matrix {
+    let mut transposed = [[0.; 4]; 4];
+    for (row, matrix_row) in matrix.iter().enumerate() {
+        for (column, x) in matrix_row.iter().enumerate() {
+            transposed[column][row] = *x;
+        }
+    }
+    transposed
+}
+
+fn invert(matrix: &Matrix) -> Option<Matrix> {
+    let mut a = *matrix;
+    let mut inverse = IDENTITY;
+    for column in 0..4 {
+        let pivot =
+            (column..4).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(column, pivot);
+        inverse.swap(column, pivot);
+
+        let scale = a[column][column];
+        for k in 0..4 {
+            a[column][k] /= scale;
+            inverse[column][k] /= scale;
+        }
+        for row in 0..4 {
+            if row != column {
+                let factor = a[row][column];
+                for k in 0..4 {
+                    a[row][k] -= factor * a[column][k];
+                    inverse[row][k] -= factor * inverse[column][k];
+                }
+            }
+        }
+    }
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rotation() {
+        let transform = Transform::rotation(Point::from_xyz(0., 0., 1.), 90.);
+
+        assert_eq!(
+            transform.apply_point(&Point::from_xyz(1., 0., 0.)),
+            Point::from_xyz(0., 1., 0.)
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let transform = Transform::translation(Point::from_xyz(1., 2., 3.))
+            * Transform::scaling(Point::from_xyz(2., 2., 2.)).unwrap();
+        let point = Point::from_xyz(1., 1., 1.);
+
+        assert_eq!(transform.apply_point(&point), Point::from_xyz(3., 4., 5.));
+        assert_eq!(
+            transform
+                .inverse()
+                .apply_point(&transform.apply_point(&point)),
+            point
+        );
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        let transform = Transform::scaling(Point::from_xyz(2., 4., 0.5)).unwrap()
+            * Transform::translation(Point::from_xyz(1., 2., 3.));
+        let inverted = Transform::from_matrix(*transform.matrix()).unwrap();
+        let point = Point::from_xyz(-1., 4., 2.);
+
+        assert_eq!(
+            inverted.inverse().apply_point(&point),
+            transform.inverse().apply_point(&point)
+        );
+        assert_eq!(Transform::from_matrix([[0.; 4]; 4]), None);
+    }
+
+    #[test]
+    fn test_apply_normal() {
+        let transform = Transform::scaling(Point::from_xyz(1., 2., 1.)).unwrap();
+        let normal = Point::from_xyz(1., 1., 0.).normalized();
+
+        assert_eq!(
+            transform.apply_normal(&normal),
+            Point::from_xyz(2., 1., 0.).normalized()
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let transform: Transform = serde_json::from_str(
+            r#"[{"Scale": [2, 2, 2]}, {"Rotate": {"axis": [0, 0, 1], "angle": 90}}, {"Translate": [0, 0, 1]}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            transform.apply_point(&Point::from_xyz(1., 0., 0.)),
+            Point::from_xyz(0., 2., 1.)
+        );
+    }
+}